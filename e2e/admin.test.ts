@@ -1,3 +1,4 @@
+import BN from 'bn.js';
 import { expect } from 'chai';
 import { Account, Contract } from 'near-api-js';
 import { FinalExecutionStatus } from 'near-api-js/lib/providers';
@@ -17,10 +18,15 @@ describe('admin', () => {
         [ito, owner, alice, bob] = await setup();
     });
 
-    it('should change treasury address', async () => {
-        await owner.change_campground_treasury_address({ args: { addr: 'hola.somenet' } } as any);
-        const newTreasuryAddress = await owner.get_treasury_address();
-        expect(newTreasuryAddress).to.be.equal('hola.somenet');
+    it('should queue treasury address change', async () => {
+        const treasuryAddress = await owner.get_treasury_address();
+        const changeId = await owner.change_campground_treasury_address({ args: { addr: 'hola.somenet' }, amount: '1' } as any);
+        const pending = await owner.get_pending_admin_changes();
+        expect(pending.map(change => change.change_id)).to.include(changeId);
+        expect(await owner.get_treasury_address()).to.be.equal(treasuryAddress);
+
+        await owner.cancel_admin_change({ args: { change_id: changeId }, amount: '1' } as any);
+        expect(await owner.get_pending_admin_changes()).to.be.empty;
     });
 
     it('should measure gas for change treasury address', async () => {
@@ -33,11 +39,12 @@ describe('admin', () => {
             args: {
                 addr: 'hola.somenet'
             },
+            attachedDeposit: new BN(1),
         });
 
         const itoPostBalance = await ito.getAccountBalance();
         const ownerPostBalance = await owner.account.getAccountBalance();
 
-        expect((tx.status as FinalExecutionStatus).SuccessValue).to.be.equal('');
+        expect((tx.status as FinalExecutionStatus).SuccessValue).to.not.be.undefined;
     });
 });
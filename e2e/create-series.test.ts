@@ -29,7 +29,8 @@ describe('create-series', () => {
             owner_id: address,
         })).to.be.true;
         const trails_by_creator = await alice.get_all_trails_by_creator({
-            creator_id: address
+            creator_id: address,
+            include_archived: null,
         });
         // expect(trails_by_creator.length).to.be.equal(1);
         expect(trails_by_creator[0].is_mintable).to.be.true;
@@ -103,7 +104,7 @@ const createTrail = async (account: ITOContract, ito: Account, price?: any) => {
         price: price || '10000000000000000000000000',
     };
 
-    const attachedDeposit = '5780000000000000000000';
+    const attachedDeposit = '7000000000000000000000';
 
     console.log('ito', await ito.getAccountBalance());
     console.log('alice', await account.account.getAccountBalance());
@@ -50,37 +50,148 @@ export type AccountId = string;
  */
 export type ValidAccountId = string;
 
+/**
+ * A fee or treasury change waiting for its timelock to expire.
+ */
+export enum AdminChange {
+    /**
+     * New Campground percentage `fee`.
+     */
+    Fee,
+
+    /**
+     * New treasury address.
+     */
+    TreasuryAddress,
+
+    /**
+     * New Campground minimum `fee`, in yoctoNEAR.
+     */
+    MinimumFee,
+
+    /**
+     * New delay for admin changes, in nanoseconds.
+     */
+    Delay,
+
+}
+
+/**
+ * An `AdminChange` queued by `change_*` methods.
+ */
+export type PendingAdminChange = {
+    /**
+     * Identifies the change for `execute_admin_change` and `cancel_admin_change`.
+     */
+    change_id: number;
+
+    /**
+     * The change to apply.
+     */
+    change: AdminChange;
+
+    /**
+     * Account that queued the change.
+     */
+    proposed_by: AccountId;
+
+    /**
+     * Block timestamp, in nanoseconds, from which the change can be executed.
+     */
+    executable_at: number;
+
+}
+
 /**
  * Provides admin operations to change different configurations of
  * the contract.
  * 
- * The contract `owner` is the only account allowed to perform these operations.
+ * Fee and treasury changes are allowed to the contract `owner`,
+ * any `Admin` and any `FinanceManager`.
+ * These changes are queued and can only be executed after `admin_change_delay`,
+ * giving creators notice. The contract `owner` can cancel them before execution.
+ * Ownership can only be transferred by the contract `owner`,
+ * and must be accepted by the proposed account.
  * Otherwise, these operations panic.
+ * 
+ * Every change requires exactly 1 yoctoNEAR attached,
+ * so it cannot be signed with a function-call access key.
  */
 export interface AdminBridge {
     /**
-     * Changes Campground percentage `fee`.
+     * Queues a change of Campground percentage `fee`, from 0 to 100.
      * When a creator creates a trail series,
      * the series takes this `fee` as a default `fee`.
+     * Returns the id of the queued change.
      */
-    change_campground_fee(args: { fee: number }, gas?: any): Promise<void>;
+    change_campground_fee(args: { fee: number }, gas?: any, amount?: any): Promise<number>;
 
     /**
-     * Changes treasury address to a new one.
+     * Queues a change of treasury address to a new one.
      * The treasury address receives the applied `fee` after an NFT
      * has been bought.
+     * Returns the id of the queued change.
+     */
+    change_campground_treasury_address(args: { addr: AccountId }, gas?: any, amount?: any): Promise<number>;
+
+    /**
+     * Queues a change of campground minimum `fee`, in yoctoNEAR,
+     * up to `MAX_CAMPGROUND_MINIMUM_FEE`.
+     * Returns the id of the queued change.
+     */
+    change_campground_minimum_fee(args: { fee: Balance }, gas?: any, amount?: any): Promise<number>;
+
+    /**
+     * Queues a change of the delay, in nanoseconds, applied to admin changes,
+     * up to `MAX_ADMIN_CHANGE_DELAY`.
+     * Only the contract `owner` and any `Admin` can change the delay.
+     * Returns the id of the queued change.
+     */
+    change_admin_change_delay(args: { delay: number }, gas?: any, amount?: any): Promise<number>;
+
+    /**
+     * Applies a queued change once its timelock has expired.
+     * Can be called by any account allowed to queue that change.
+     */
+    execute_admin_change(args: { change_id: number }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Discards a queued change before it is executed.
+     */
+    cancel_admin_change(args: { change_id: number }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Returns all the changes waiting to be executed.
      */
-    change_campground_treasury_address(args: { addr: AccountId }, gas?: any): Promise<void>;
+    get_pending_admin_changes(): Promise<PendingAdminChange[]>;
 
     /**
-     * Changes campground minimum `fee`, in yoctoNEAR.
+     * Returns the delay, in nanoseconds, applied to admin changes.
      */
-    change_campground_minimum_fee(args: { fee: Balance }, gas?: any): Promise<void>;
+    get_admin_change_delay(): Promise<number>;
 
     /**
-     * Adds a setting key-val to the map
+     * Proposes `new_owner_id` as the new contract `owner`.
+     * Ownership is not transferred until `new_owner_id` calls `accept_ownership`.
+     * A new proposal replaces any pending one.
      */
-    add_setting(args: { key: string, value: string }, gas?: any): Promise<void>;
+    propose_owner(args: { new_owner_id: AccountId }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Makes the caller the contract `owner`.
+     * Only the account proposed by `propose_owner` can accept the ownership.
+     */
+    accept_ownership(gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Cancels the pending ownership transfer.
+     */
+    cancel_ownership_proposal(gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Returns the account proposed as the new contract `owner`, if any.
+     */
+    get_pending_owner(): Promise<AccountId|null>;
 
 }
 
@@ -150,13 +261,24 @@ export interface SeriesBridge {
     /**
      * Verifies whether a given user, `AccountId` owns the copy of a trail.
      */
-    is_owner(args: { series_id: TrailIdAndCopyNumber, owner_id: AccountId }): Promise<boolean>;
+    is_owner(args: { series_id: TrailId, owner_id: AccountId }): Promise<boolean>;
+
+    /**
+     * Returns how many copies of a trail a given user, `AccountId`, owns.
+     */
+    get_owned_copies_count(args: { series_id: TrailId, owner_id: AccountId }): Promise<number>;
 
     /**
      * Verifies whether a given user, `AccountId` is the creator of a given trail.
      */
     is_creator(args: { series_id: TrailId, owner_id: AccountId }): Promise<boolean>;
 
+    /**
+     * Returns a trail copy along with its trail series, `null` if not found.
+     * Campground variant of `nft_token`.
+     */
+    trail_ticket(args: { token_id: TrailIdAndCopyNumber }): Promise<JsonTrail|null>;
+
     /**
      * Returns the business information of a trail, `null` otherwise.
      */
@@ -164,13 +286,53 @@ export interface SeriesBridge {
 
     /**
      * Returns all the trail copies owned by a given user, `AccountId`.
+     * Archived trails are left out unless `include_archived` is `true`.
      */
-    get_all_trails_by_owner(args: { owner_id: AccountId }): Promise<TrailSeries[]>;
+    get_all_trails_by_owner(args: { owner_id: AccountId, include_archived: boolean|null }): Promise<TrailSeries[]>;
 
     /**
      * Returns all the trails created by a given user (AccountId).
+     * Archived trails are left out unless `include_archived` is `true`.
      */
-    get_all_trails_by_creator(args: { creator_id: AccountId }): Promise<TrailSeries[]>;
+    get_all_trails_by_creator(args: { creator_id: AccountId, include_archived: boolean|null }): Promise<TrailSeries[]>;
+
+    /**
+     * Returns up to `limit` trails (50 by default) starting at `from_index`.
+     * Trails are listed in creation order, except that deleting a trail
+     * moves the last listed one into its place.
+     * Archived trails are left out unless `include_archived` is `true`.
+     * `from_index` is a position among the trails listed, so a page shorter than `limit`
+     * is the last one. Leaving archived trails out costs gas linear in `from_index`.
+     */
+    get_trails(args: { from_index: U128|null, limit: number|null, include_archived: boolean|null }): Promise<JsonTrailSeries[]>;
+
+    /**
+     * Returns the number of trails, including archived ones.
+     */
+    get_trails_count(): Promise<number>;
+
+    /**
+     * Paginated version of `get_all_trails_by_creator`, see `get_trails`.
+     */
+    get_trails_by_creator(args: { creator_id: AccountId, from_index: U128|null, limit: number|null, include_archived: boolean|null }): Promise<JsonTrailSeries[]>;
+
+    /**
+     * Returns the number of trails created by `creator_id`, including archived ones.
+     */
+    get_trails_count_by_creator(args: { creator_id: AccountId }): Promise<number>;
+
+    /**
+     * Paginated version of `get_all_trails_by_owner`, see `get_trails`.
+     * Each trail is listed once, however many copies `owner_id` holds,
+     * in the order first acquired. Giving away the last copy of a trail
+     * moves the last listed one into its place.
+     */
+    get_trails_by_owner(args: { owner_id: AccountId, from_index: U128|null, limit: number|null, include_archived: boolean|null }): Promise<JsonTrailSeries[]>;
+
+    /**
+     * Returns the number of trails `owner_id` holds copies of, including archived ones.
+     */
+    get_trails_count_by_owner(args: { owner_id: AccountId }): Promise<number>;
 
     /**
      * Returns the current minimum fee in YoctoNEAR by campground.
@@ -198,145 +360,718 @@ export interface SeriesBridge {
     is_trail_mintable(args: { trail_id: TrailId }): Promise<boolean>;
 
     /**
-     * Verifies there is a whitelisted address
+     * Whether the metadata of a trail has been permanently frozen by its creator.
+     */
+    is_trail_frozen(args: { trail_id: TrailId }): Promise<boolean>;
+
+    /**
+     * Whether a trail has been archived by its creator.
+     */
+    is_trail_archived(args: { trail_id: TrailId }): Promise<boolean>;
+
+}
+
+/**
+ * Known configuration keys of the contract.
+ * 
+ * These keys are new, not migrated from the free-form `settings` used up to `v3`.
+ * Those settings only held the sub-admin and fiat minter accounts,
+ * which are migrated to the `SubAdmin` and `FiatMinter` roles instead.
+ */
+export enum ConfigKey {
+    /**
+     * Trails must be priced lower than this amount, in yoctoNEAR.
+     */
+    MaxPrice,
+
+    /**
+     * Media shown for trails without their own `media` nor `fallback_media`.
+     */
+    FallbackMedia,
+
+    /**
+     * Maximum length in bytes of trail and resource titles.
+     */
+    MaxTitleLength,
+
+    /**
+     * Maximum length in bytes of trail and resource descriptions.
+     */
+    MaxDescriptionLength,
+
+    /**
+     * Maximum length in bytes of the trail `data` and resource `extra`.
+     */
+    MaxExtraLength,
+
+    /**
+     * Maximum length in bytes of every `media` and `reference` URI.
+     */
+    MaxUriLength,
+
+    /**
+     * Maximum number of resources per trail.
+     */
+    MaxResources,
+
+}
+
+/**
+ * A configuration key along with its typed value.
+ */
+export enum ConfigEntry {
+    /**
+     * Must be greater than 0 and not higher than `MAX_PRICE`.
+     */
+    MaxPrice,
+
+    /**
+     * Must not be empty, `None` leaves trails without media.
+     * Relative paths are resolved against `base_uri`, otherwise
+     * the scheme must be one of `ALLOWED_URI_SCHEMES`.
+     */
+    FallbackMedia,
+
+    /**
+     * Must be between 1 and `MAX_METADATA_LIMIT`, as every limit below.
+     */
+    MaxTitleLength,
+
+    /**
+     */
+    MaxDescriptionLength,
+
+    /**
+     */
+    MaxExtraLength,
+
+    /**
+     */
+    MaxUriLength,
+
+    /**
+     */
+    MaxResources,
+
+}
+
+/**
+ * Provides operations to manage the contract configuration.
+ * 
+ * Only the contract `owner` and any `Admin` can change the configuration,
+ * attaching exactly 1 yoctoNEAR.
+ */
+export interface ConfigBridge {
+    /**
+     * Sets the value of a configuration key, validating it first.
+     */
+    set_config(args: { entry: ConfigEntry }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Restores a configuration key to its default value.
+     * Returns `false` if the key already had its default value.
+     */
+    remove_config(args: { key: ConfigKey }, gas?: any, amount?: any): Promise<boolean>;
+
+    /**
+     * Returns the current value of a configuration key.
+     */
+    get_config(args: { key: ConfigKey }): Promise<ConfigEntry>;
+
+    /**
+     * Returns the current value of every configuration key.
+     */
+    get_configs(): Promise<ConfigEntry[]>;
+
+}
+
+/**
+ * Provides operations to deal with trail series.
+ */
+export interface CreateTrailSeries {
+    /**
+     * Returns the deposit `create_trail_series` needs for its storage, with the same arguments.
+     * It is exact when `creator_id` is given, as views do not know their caller.
+     * Otherwise it assumes a first-time creator with the longest account id,
+     * and `create_trail_series` refunds the difference.
+     */
+    create_trail_series_estimated(args: { metadata: TrailSeriesMetadata, price: U128|null, creator_id: AccountId|null, creator_royalty: U128|null, allow_user_minting: boolean|null, slug: TrailId|null }): Promise<U128|null>;
+
+    /**
+     * Creates a series (trail) inside the smart contract.
+     * The trail is identified by `slug` when given,
+     * otherwise by the next number in the contract counter.
+     */
+    create_trail_series(args: { metadata: TrailSeriesMetadata, price: U128|null, creator_id: AccountId|null, creator_royalty: U128|null, allow_user_minting: boolean|null, slug: TrailId|null }, gas?: any, amount?: any): Promise<JsonTrail>;
+
+    /**
+     * Replaces the metadata and resources of a trail.
+     * Only the trail creator can update it, as long as it is not frozen.
+     * `tickets_amount` is kept from the current metadata.
+     */
+    update_trail_series_metadata(args: { trail_id: TrailId, metadata: TrailSeriesMetadata }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Permanently freezes the metadata and resources of a trail.
+     * Only the trail creator can freeze it, and it cannot be undone,
+     * so it requires exactly 1 yoctoNEAR attached.
+     * The storage recording the freeze is covered by the contract.
+     */
+    freeze_trail_series(args: { trail_id: TrailId }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Increases or reduces the `total` supply of a trail.
+     * The new `total` cannot be lower than the circulating copies.
+     * `supply.total` is authoritative: `metadata.tickets_amount` keeps the supply
+     * the trail was created with, so frozen metadata is never rewritten.
+     * Only the trail creator can change it.
+     */
+    update_trail_series_supply(args: { trail_id: TrailId, total: number }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Changes the `price` of a trail, recalculating the Campground fee with
+     * the current fee configuration. Copies already sold are not affected.
+     * Only the trail creator can change it, as long as `UpdatePrice` is not paused.
+     * Freezing a trail does not lock its price.
+     */
+    update_trail_series_price(args: { trail_id: TrailId, price: U128 }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Closes the sale of a trail by reducing its `total` supply to the circulating copies.
+     * The sale can be reopened later with `update_trail_series_supply`.
+     */
+    close_trail_series_sale(args: { trail_id: TrailId }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Deletes a trail without circulating copies,
+     * refunding the storage it used to its creator.
+     * Frozen trails can be deleted too, as no collector holds a copy yet.
+     */
+    delete_trail_series(args: { trail_id: TrailId }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Archives a trail, hiding it from listings.
+     * Copies already minted are kept, but no more copies can be minted.
+     * Can be called by the trail creator, the contract `owner`, any `Admin` or any `Moderator`.
+     */
+    archive_trail_series(args: { trail_id: TrailId }, gas?: any, amount?: any): Promise<void>;
+
+    /**
+     * Restores an archived trail back into listings.
+     * Can be called by the trail creator, the contract `owner`, any `Admin` or any `Moderator`.
+     */
+    unarchive_trail_series(args: { trail_id: TrailId }, gas?: any): Promise<void>;
+
+}
+
+/**
+ * NEP-181 interface definition.
+ * 
+ * See https://nomicon.io/Standards/Tokens/NonFungibleToken/Enumeration.
+ */
+export interface NonFungibleTokenEnumeration {
+    /**
+     * Query for the total supply of NFTs on the contract.
+     */
+    nft_total_supply(): Promise<U128>;
+
+    /**
+     * Query for nft tokens on the contract regardless of the owner using pagination.
+     * Query for nft tokens on the contract regardless of the owner using pagination,
+     * in mint order.
+     */
+    nft_tokens(args: { from_index: U128|null, limit: number|null }): Promise<JsonToken[]>;
+
+    /**
+     * Campground variant of `nft_tokens`, along with the trail series of each token.
+     */
+    trail_tickets(args: { from_index: U128|null, limit: number|null }): Promise<JsonTrail[]>;
+
+    /**
+     * Get the total supply of NFTs for a given owner.
+     * Get the total supply of NFTs for a given owner.
+     */
+    nft_supply_for_owner(args: { account_id: AccountId }): Promise<U128>;
+
+    /**
+     * Query all tokens of an owner.
+     * Similar to get_all_trails_by_owner with pagination.
+     * Query up to `limit` tokens of an owner (50 by default), in mint order.
+     * Similar to get_all_trails_by_owner with pagination.
+     * `from_index` is a position in the owner's own tokens, as per NEP-181.
+     * Skipping to `from_index` costs gas linear in `from_index`,
+     * prefer `trail_tickets_for_owner_after` to go through large collections.
+     */
+    trail_tickets_for_owner(args: { account_id: AccountId, from_index: U128|null, limit: number|null }): Promise<JsonTrail[]>;
+
+    /**
+     * Query up to `limit` tokens of an owner (50 by default), in mint order,
+     * starting right after the token `after`, or from the first token if not given.
+     * Passing the last token of a page fetches the next page, at the same cost as the first one.
+     */
+    trail_tickets_for_owner_after(args: { account_id: AccountId, after: TrailIdAndCopyNumber|null, limit: number|null }): Promise<JsonTrail[]>;
+
+    /**
+     * Get list of all tokens owned by a given account
+     * Get list of all tokens owned by a given account, paginated as `trail_tickets_for_owner`.
+     */
+    nft_tokens_for_owner(args: { account_id: AccountId, from_index: U128|null, limit: number|null }): Promise<JsonToken[]>;
+
+    /**
+     * Get the number of copies minted for a given trail series.
+     */
+    nft_supply_for_series(args: { series_id: TrailId }): Promise<U128>;
+
+    /**
+     * Query the copies minted for a trail series using pagination, in mint order.
+     */
+    nft_tokens_for_series(args: { series_id: TrailId, from_index: U128|null, limit: number|null }): Promise<JsonToken[]>;
+
+    /**
+     * Campground variant of `nft_tokens_for_series`, along with the trail series.
+     */
+    trail_tickets_for_series(args: { series_id: TrailId, from_index: U128|null, limit: number|null }): Promise<JsonTrail[]>;
+
+    /**
+     * Query the distinct holders of a trail series along with their copy counts,
+     * using pagination.
+     */
+    nft_holders_for_series(args: { series_id: TrailId, from_index: U128|null, limit: number|null }): Promise<SeriesHolder[]>;
+
+}
+
+/**
+ */
+export enum NearEvent {
+    /**
+     */
+    Nep171,
+
+    /**
+     */
+    Campground,
+
+}
+
+/**
+ */
+export type Nep171Event = {
+    /**
+     */
+    version: string;
+
+    /**
+     */
+    event_kind: Nep171EventKind;
+
+}
+
+/**
+ */
+export enum Nep171EventKind {
+    /**
+     */
+    NftMint,
+
+    /**
+     */
+    NftTransfer,
+
+}
+
+/**
+ */
+export type CampgroundEvent = {
+    /**
+     */
+    version: string;
+
+    /**
+     */
+    event_kind: CampgroundEventKind;
+
+}
+
+/**
+ */
+export enum CampgroundEventKind {
+    /**
+     */
+    SeriesCreate,
+
+    /**
+     */
+    SeriesPriceUpdate,
+
+    /**
+     */
+    SeriesMintabilityUpdate,
+
+    /**
+     */
+    SeriesPurchase,
+
+    /**
+     */
+    SeriesSupplyUpdate,
+
+    /**
+     */
+    SeriesSaleClose,
+
+    /**
+     */
+    SeriesFreeze,
+
+    /**
+     */
+    SeriesMetadataUpdate,
+
+    /**
+     */
+    RoleGrant,
+
+    /**
+     */
+    RoleRevoke,
+
+    /**
+     */
+    OwnershipTransferPropose,
+
+    /**
+     */
+    OwnershipTransferAccept,
+
+    /**
+     */
+    OwnershipTransferCancel,
+
+    /**
+     */
+    ContractPause,
+
+    /**
+     */
+    ContractUnpause,
+
+    /**
+     */
+    AdminChangeQueue,
+
+    /**
+     */
+    AdminChangeExecute,
+
+    /**
+     */
+    AdminChangeCancel,
+
+    /**
+     */
+    ConfigUpdate,
+
+}
+
+/**
+ */
+export type NftMintData = {
+    /**
+     */
+    owner_id: string;
+
+    /**
+     */
+    token_ids: string[];
+
+    /**
+     */
+    memo: string|null;
+
+}
+
+/**
+ */
+export type NftTransferData = {
+    /**
+     */
+    authorized_id: string|null;
+
+    /**
+     */
+    old_owner_id: string;
+
+    /**
+     */
+    new_owner_id: string;
+
+    /**
+     */
+    token_ids: string[];
+
+    /**
+     */
+    memo: string|null;
+
+}
+
+/**
+ */
+export type SeriesCreateData = {
+    /**
+     */
+    series_id: string;
+
+    /**
+     */
+    creator_id: string;
+
+    /**
+     */
+    price: U128;
+
+    /**
+     */
+    total: number;
+
+    /**
+     */
+    is_mintable: boolean;
+
+}
+
+/**
+ */
+export type SeriesPriceData = {
+    /**
+     */
+    series_id: string;
+
+    /**
+     */
+    previous_price: U128;
+
+    /**
+     */
+    price: U128;
+
+}
+
+/**
+ */
+export type SeriesMintabilityData = {
+    /**
+     */
+    series_id: string;
+
+    /**
+     */
+    is_mintable: boolean;
+
+    /**
+     */
+    sender_id: string;
+
+}
+
+/**
+ */
+export type SeriesFreezeData = {
+    /**
+     */
+    series_id: string;
+
+    /**
+     */
+    creator_id: string;
+
+}
+
+/**
+ * `updated_at` is in milliseconds since the Unix epoch.
+ */
+export type SeriesMetadataData = {
+    /**
+     */
+    series_id: string;
+
+    /**
+     */
+    creator_id: string;
+
+    /**
+     */
+    updated_at: number;
+
+}
+
+/**
+ * How a copy was paid for.
+ */
+export enum PaymentMethod {
+    /**
+     * Bought with NEAR through `nft_buy_series`.
+     */
+    Near,
+
+    /**
+     * Paid off-chain and minted by a `FiatMinter` through `nft_mint`.
+     */
+    FiatMinted,
+
+    /**
+     * Minted by the trail creator through `nft_mint`.
+     */
+    CreatorMinted,
+
+}
+
+/**
+ * Amounts are in yoctoNEAR.
+ * `amount` is the price paid on-chain, split into `campground_fee` and `creator_proceeds`,
+ * so it is 0 for copies paid off-chain or minted by the creator.
+ * `storage_deposit` is the deposit attached to cover storage instead,
+ * and `refund` the part of the attached deposit sent back to the buyer.
+ */
+export type PurchaseData = {
+    /**
+     */
+    buyer_id: string;
+
+    /**
+     */
+    receiver_id: string;
+
+    /**
+     */
+    series_id: string;
+
+    /**
+     */
+    token_id: string;
+
+    /**
+     */
+    amount: U128;
+
+    /**
+     */
+    campground_fee: U128;
+
+    /**
      */
-    is_there_whitelisted_address(): Promise<boolean>;
+    creator_proceeds: U128;
 
     /**
-     * Gets the address of a whitelisted address
      */
-    get_whitelisted_address(): Promise<AccountId>;
+    payment_method: PaymentMethod;
 
-}
-
-/**
- * Provides operations to deal with trail series.
- */
-export interface CreateTrailSeries {
     /**
      */
-    create_trail_series_estimated(args: { metadata: TrailSeriesMetadata, price: U128|null, creator_id: AccountId|null, creator_royalty: U128|null, allow_user_minting: boolean|null }): Promise<U128|null>;
+    storage_deposit: U128;
 
     /**
-     * Creates a series (trail) inside the smart contract.
      */
-    create_trail_series(args: { metadata: TrailSeriesMetadata, price: U128|null, creator_id: AccountId|null, creator_royalty: U128|null, allow_user_minting: boolean|null }, gas?: any, amount?: any): Promise<JsonTrail>;
+    refund: U128;
 
 }
 
 /**
- * NEP-181 interface definition.
- * 
- * See https://nomicon.io/Standards/Tokens/NonFungibleToken/Enumeration.
  */
-export interface NonFungibleTokenEnumeration {
-    /**
-     * Query for the total supply of NFTs on the contract.
-     */
-    nft_total_supply(): Promise<U128>;
-
+export type SeriesSupplyData = {
     /**
-     * Query for nft tokens on the contract regardless of the owner using pagination.
-     * Query for nft tokens on the contract regardless of the owner using pagination.
      */
-    nft_tokens(args: { from_index: U128|null, limit: number|null }): Promise<JsonTrail[]>;
+    series_id: string;
 
     /**
-     * Get the total supply of NFTs for a given owner.
-     * Get the total supply of NFTs for a given owner.
      */
-    nft_supply_for_owner(args: { account_id: AccountId }): Promise<U128>;
+    previous_total: number;
 
     /**
-     * Query all tokens of an owner.
-     * Similar to get_all_trails_by_owner with pagination.
-     * Query all tokens of an owner.
-     * Similar to get_all_trails_by_owner with pagination.
      */
-    trail_tickets_for_owner(args: { account_id: AccountId, from_index: U128|null, limit: number|null }): Promise<JsonTrail[]>;
+    total: number;
 
     /**
-     * Get list of all tokens owned by a given account
-     * Get list of all tokens owned by a given account
      */
-    nft_tokens_for_owner(args: { account_id: AccountId, from_index: U128|null, limit: number|null }): Promise<JsonTrail[]>;
+    circulating: number;
 
 }
 
 /**
  */
-export enum NearEvent {
+export type RoleData = {
     /**
      */
-    Nep171,
+    role: Role;
+
+    /**
+     */
+    account_id: string;
+
+    /**
+     */
+    sender_id: string;
 
 }
 
 /**
  */
-export type Nep171Event = {
+export type OwnershipTransferData = {
     /**
      */
-    version: string;
+    old_owner_id: string;
 
     /**
      */
-    event_kind: Nep171EventKind;
+    new_owner_id: string;
 
 }
 
 /**
  */
-export enum Nep171EventKind {
+export type PauseData = {
     /**
      */
-    NftMint,
+    operation: PausableOperation;
 
     /**
      */
-    NftTransfer,
+    sender_id: string;
 
 }
 
 /**
  */
-export type NftMintData = {
+export type AdminChangeData = {
     /**
      */
-    owner_id: string;
+    change_id: number;
 
     /**
      */
-    token_ids: string[];
+    change: AdminChange;
 
     /**
      */
-    memo: string|null;
+    executable_at: number;
 
 }
 
 /**
+ * `value` is `None` when the key is restored to its default.
  */
-export type NftTransferData = {
-    /**
-     */
-    authorized_id: string|null;
-
-    /**
-     */
-    old_owner_id: string;
-
+export type ConfigData = {
     /**
      */
-    new_owner_id: string;
+    key: ConfigKey;
 
     /**
      */
-    token_ids: string[];
+    value: ConfigEntry|null;
 
     /**
      */
-    memo: string|null;
+    sender_id: string;
 
 }
 
@@ -491,6 +1226,7 @@ export type TrailSeriesMetadata = {
     description: string;
 
     /**
+     * Supply the trail was created with, `supply.total` holds the current one
      */
     tickets_amount: number;
 
@@ -525,6 +1261,16 @@ export type TrailSeriesMetadata = {
      */
     campground_id: string;
 
+    /**
+     * Base64-encoded sha256 hash of the content referenced by `media`.
+     */
+    media_hash: Base64VecU8|null;
+
+    /**
+     * Media shown when `media` is not set, instead of the `FallbackMedia` configuration.
+     */
+    fallback_media: string|null;
+
 }
 
 /**
@@ -632,6 +1378,18 @@ export type TrailSeries = {
      */
     royalties: Record<AccountId, number>;
 
+    /**
+     * Whether the creator has permanently frozen the metadata of this series.
+     * Not stored along the series, it is filled in from `frozen_trails` on read.
+     */
+    is_frozen: boolean;
+
+    /**
+     * Whether the creator has archived this series, hiding it from listings.
+     * Not stored along the series, it is filled in from `archived_trails` on read.
+     */
+    is_archived: boolean;
+
 }
 
 /**
@@ -643,12 +1401,20 @@ export type TrailBusiness = {
     owner_id: AccountId;
 
     /**
+     * Trail series the copy belongs to.
      */
     token_id: TrailId;
 
     /**
+     * Copy number within the series, starting at 1.
+     */
+    copy_number: number;
+
+    /**
+     * When the copy was minted, Unix epoch in milliseconds.
+     * `None` for copies minted before it was recorded.
      */
-    partial_metadata: TokenMetadata;
+    issued_at: number|null;
 
 }
 
@@ -678,6 +1444,65 @@ export type JsonTrail = {
 
 }
 
+/**
+ * Token as defined by NEP-171, returned by the standard `nft_*` views.
+ * `JsonTrail` is the Campground variant, along with the trail series.
+ */
+export type JsonToken = {
+    /**
+     * token ID
+     */
+    token_id: TrailIdAndCopyNumber;
+
+    /**
+     * owner of the token
+     */
+    owner_id: AccountId;
+
+    /**
+     * NEAR metadata
+     */
+    metadata: TokenMetadata;
+
+    /**
+     * Trails do not support approvals yet, so this is always empty.
+     */
+    approved_account_ids: Record<AccountId, number>;
+
+}
+
+/**
+ * An account holding copies of a trail series.
+ */
+export type SeriesHolder = {
+    /**
+     * holder of the copies
+     */
+    account_id: AccountId;
+
+    /**
+     * number of copies held
+     */
+    copies: number;
+
+}
+
+/**
+ * A trail series along with its ID, as returned by paginated views.
+ */
+export type JsonTrailSeries = {
+    /**
+     * trail ID
+     */
+    series_id: TrailId;
+
+    /**
+     * trail series
+     */
+    series: TrailSeries;
+
+}
+
 /**
  * NEP-177 interface definition.
  * 
@@ -691,21 +1516,209 @@ export interface NonFungibleTokenMetadata {
 
 }
 
+/**
+ * Steps of the reindex started by `migrate_v3_to_v4`, run in this order.
+ */
+export enum MigrationStep {
+    /**
+     * Rewrites every trail series with `media_hash` and `fallback_media`.
+     */
+    Trails,
+
+    /**
+     * Detaches every creator from the set of trails they shared up to `v3`.
+     */
+    DetachCreators,
+
+    /**
+     * Clears the set of trails shared by every creator up to `v3`.
+     */
+    ClearSharedCreatorSet,
+
+    /**
+     * Indexes every trail series under its creator's own set.
+     */
+    IndexCreators,
+
+    /**
+     * Compacts every token and indexes it by mint order, trail series and holder.
+     */
+    Tokens,
+
+}
+
+/**
+ * Completes the migration from `v3` in batches,
+ * as reindexing every trail and token at once would not fit in a single transaction.
+ * 
+ * While the migration is in progress, every trail view and every pausable operation
+ * panics, as trails and tokens are not all in the current layout yet.
+ * Only the contract `owner` can run a batch, attaching exactly 1 yoctoNEAR.
+ */
+export interface MigrationBridge {
+    /**
+     * Processes up to `limit` trails or tokens, `DEFAULT_MIGRATION_BATCH` by default,
+     * resuming where the previous batch stopped.
+     * Returns `true` once the migration is complete.
+     */
+    migrate_v4_batch(args: { limit: number|null }, gas?: any, amount?: any): Promise<boolean>;
+
+    /**
+     * Returns the step being run, or `None` if no migration is in progress.
+     */
+    get_migration_step(): Promise<MigrationStep|null>;
+
+}
+
 /**
  */
 export interface NonFungibleTokenCore {
     /**
+     * Requires exactly 1 yoctoNEAR attached, as per NEP-171.
      */
     nft_transfer(args: { receiver_id: AccountId, token_id: TrailIdAndCopyNumber, memo: string|null }, gas?: any, amount?: any): Promise<void>;
 
     /**
      * Returns `true` if the token was transferred from the sender's account.
+     * Requires exactly 1 yoctoNEAR attached, as per NEP-171.
      */
     nft_transfer_call(args: { receiver_id: AccountId, token_id: TrailId, memo: string|null, msg: string }, gas?: any, amount?: any): Promise<void>;
 
     /**
      */
-    nft_token(args: { token_id: TrailIdAndCopyNumber }): Promise<JsonTrail|null>;
+    nft_token(args: { token_id: TrailIdAndCopyNumber }): Promise<JsonToken|null>;
+
+}
+
+/**
+ * Classes of operations that can be paused contract-wide.
+ */
+export enum PausableOperation {
+    /**
+     * Public purchases through `nft_buy_series`.
+     */
+    Buy,
+
+    /**
+     * Direct mints through `nft_mint`.
+     */
+    Mint,
+
+    /**
+     * Trail creation through `create_trail_series`.
+     */
+    Create,
+
+    /**
+     * Token transfers through `nft_transfer` and `nft_transfer_call`.
+     */
+    Transfer,
+
+    /**
+     * Price changes through `update_trail_series_price`.
+     */
+    UpdatePrice,
+
+}
+
+/**
+ * Provides an emergency stop for each `PausableOperation`,
+ * without having to go through every trail.
+ * 
+ * Only the contract `owner`, any `Admin` and any `Guardian` can pause or unpause operations,
+ * attaching exactly 1 yoctoNEAR.
+ */
+export interface PauseBridge {
+    /**
+     * Pauses `operation` until `unpause` is called.
+     * Returns `false` if `operation` was already paused.
+     */
+    pause(args: { operation: PausableOperation }, gas?: any, amount?: any): Promise<boolean>;
+
+    /**
+     * Resumes a paused `operation`.
+     * Returns `false` if `operation` was not paused.
+     */
+    unpause(args: { operation: PausableOperation }, gas?: any, amount?: any): Promise<boolean>;
+
+    /**
+     * Whether `operation` is currently paused.
+     */
+    is_paused(args: { operation: PausableOperation }): Promise<boolean>;
+
+    /**
+     * Returns all the operations currently paused.
+     */
+    get_paused_operations(): Promise<PausableOperation[]>;
+
+}
+
+/**
+ * Roles that can be granted to accounts to operate the contract.
+ * The contract `owner` is implicitly an `Admin`.
+ */
+export enum Role {
+    /**
+     * Manages the configuration and grants or revokes every role but `Admin`.
+     */
+    Admin,
+
+    /**
+     * Toggles user minting of any trail.
+     */
+    SubAdmin,
+
+    /**
+     * Mints copies of any trail on behalf of fiat buyers.
+     */
+    FiatMinter,
+
+    /**
+     * Archives or unarchives any trail.
+     */
+    Moderator,
+
+    /**
+     * Changes Campground fees and treasury address.
+     */
+    FinanceManager,
+
+    /**
+     * Pauses or unpauses contract operations in an emergency.
+     */
+    Guardian,
+
+}
+
+/**
+ * Provides operations to manage the members of each `Role`.
+ * 
+ * Only the contract `owner` can grant or revoke the `Admin` role.
+ * The remaining roles can also be granted or revoked by any `Admin`.
+ * Granting and revoking require exactly 1 yoctoNEAR attached.
+ */
+export interface RolesBridge {
+    /**
+     * Grants `role` to `account_id`.
+     * Returns `false` if `account_id` already had that role.
+     */
+    grant_role(args: { role: Role, account_id: AccountId }, gas?: any, amount?: any): Promise<boolean>;
+
+    /**
+     * Revokes `role` from `account_id`.
+     * Returns `false` if `account_id` did not have that role.
+     */
+    revoke_role(args: { role: Role, account_id: AccountId }, gas?: any, amount?: any): Promise<boolean>;
+
+    /**
+     * Whether `account_id` has been granted `role`.
+     */
+    has_role(args: { role: Role, account_id: AccountId }): Promise<boolean>;
+
+    /**
+     * Returns all the accounts that have been granted `role`.
+     */
+    get_role_members(args: { role: Role }): Promise<AccountId[]>;
 
 }
 
@@ -723,18 +1736,68 @@ export interface NonFungibleTokenPayouts {
 }
 
 /**
- * This trait complies with functions that can only be accessed by settings.SUB_ADMIN_ADDRESS
+ * Storage a call would add to the contract, along with the deposit to attach.
+ */
+export type StorageEstimate = {
+    /**
+     * Bytes the call adds to the contract storage, `0` when it frees storage.
+     */
+    bytes: U64;
+
+    /**
+     * Cost of those bytes, in yoctoNEAR.
+     */
+    storage_cost: U128;
+
+    /**
+     * Exact deposit to attach to the call, in yoctoNEAR.
+     */
+    deposit: U128;
+
+}
+
+/**
+ * Provides views to estimate the storage of the calls that write state,
+ * computed from the serialized structures each call would write.
+ * 
+ * Estimates are exact for the current state, so they should be requested
+ * right before the call. For `create_trail_series`, see `create_trail_series_estimated`.
+ */
+export interface StorageEstimateBridge {
+    /**
+     * Estimates `nft_mint` of the next copy of `token_id` for `receiver_id`.
+     * The deposit covers the storage of the copy, as copies minted directly are paid off-chain.
+     */
+    nft_mint_estimated(args: { token_id: TrailId, receiver_id: AccountId }): Promise<StorageEstimate>;
+
+    /**
+     * Estimates `nft_buy_series` of the next copy of `trail_series_id` for `receiver_id`.
+     * The deposit is the price of the trail, storage is covered by the contract.
+     */
+    nft_buy_series_estimated(args: { trail_series_id: TrailId, receiver_id: AccountId }): Promise<StorageEstimate>;
+
+    /**
+     * Estimates `nft_transfer` of `token_id` to `receiver_id`.
+     * The deposit is the 1 yoctoNEAR required, storage is covered by the contract.
+     */
+    nft_transfer_estimated(args: { receiver_id: AccountId, token_id: TrailIdAndCopyNumber }): Promise<StorageEstimate>;
+
+}
+
+/**
+ * This trait complies with functions that can only be accessed by accounts with the `SubAdmin` role
+ * Changes require exactly 1 yoctoNEAR attached
  */
 export interface SubAdminBridge {
     /**
      * Remove a trail from nonmintable_trails so that it can be minted by users again
      */
-    remove_trail_from_nonmintable_list(args: { trail_id: TrailId }, gas?: any): Promise<boolean>;
+    remove_trail_from_nonmintable_list(args: { trail_id: TrailId }, gas?: any, amount?: any): Promise<boolean>;
 
     /**
      * Includes a trail in the list of non-user mintable
      */
-    insert_trail_from_nonmintable_list(args: { trail_id: TrailId }, gas?: any): Promise<boolean>;
+    insert_trail_from_nonmintable_list(args: { trail_id: TrailId }, gas?: any, amount?: any): Promise<boolean>;
 
     /**
      * Panic if caller is not able to toggle
@@ -745,69 +1808,111 @@ export interface SubAdminBridge {
      * Toggle minting for all trails
      * enable_minting must be true to make all trails accept public minting (nft_buy_series)
      * Or false to lock them all
+     * For a contract-wide emergency stop, use `pause` instead.
      */
-    toggle_for_all(args: { enable_minting: boolean }, gas?: any): Promise<void>;
+    toggle_for_all(args: { enable_minting: boolean }, gas?: any, amount?: any): Promise<void>;
 
     /**
      * Verifies whether caller is subadmin
      */
     is_caller_subadmin(): Promise<boolean>;
 
-    /**
-     * Gets accountId of sub admin
-     */
-    get_subadmin(): Promise<AccountId>;
-
 }
 
-export interface Contract extends AdminBridge, NonFungibleTokenApproval, SeriesBridge, CreateTrailSeries, NonFungibleTokenEnumeration, NonFungibleTokenMetadata, NonFungibleTokenCore, NonFungibleTokenPayouts, SubAdminBridge {}
+export interface Contract extends AdminBridge, NonFungibleTokenApproval, SeriesBridge, ConfigBridge, CreateTrailSeries, NonFungibleTokenEnumeration, NonFungibleTokenMetadata, MigrationBridge, NonFungibleTokenCore, PauseBridge, RolesBridge, NonFungibleTokenPayouts, StorageEstimateBridge, SubAdminBridge {}
 
 export const ContractMethods = {
     viewMethods: [
+        "get_pending_admin_changes",
+        "get_admin_change_delay",
+        "get_pending_owner",
         "nft_is_approved",
         "series_exists",
         "get_owner",
         "get_trail_by_id_optional",
         "get_trail_by_id",
         "is_owner",
+        "get_owned_copies_count",
         "is_creator",
+        "trail_ticket",
         "get_trail_business",
         "get_all_trails_by_owner",
         "get_all_trails_by_creator",
+        "get_trails",
+        "get_trails_count",
+        "get_trails_by_creator",
+        "get_trails_count_by_creator",
+        "get_trails_by_owner",
+        "get_trails_count_by_owner",
         "get_current_fee",
         "get_fee_percentage",
         "get_treasury_address",
         "is_caller_contract_owner",
         "is_trail_mintable",
-        "is_there_whitelisted_address",
-        "get_whitelisted_address",
+        "is_trail_frozen",
+        "is_trail_archived",
+        "get_config",
+        "get_configs",
         "create_trail_series_estimated",
         "nft_total_supply",
         "nft_tokens",
+        "trail_tickets",
         "nft_supply_for_owner",
         "trail_tickets_for_owner",
+        "trail_tickets_for_owner_after",
         "nft_tokens_for_owner",
+        "nft_supply_for_series",
+        "nft_tokens_for_series",
+        "trail_tickets_for_series",
+        "nft_holders_for_series",
         "nft_get_series_price",
         "nft_metadata",
+        "get_migration_step",
         "nft_token",
+        "is_paused",
+        "get_paused_operations",
+        "has_role",
+        "get_role_members",
         "nft_payout",
+        "nft_mint_estimated",
+        "nft_buy_series_estimated",
+        "nft_transfer_estimated",
         "verify_toggle_minting_caller",
         "is_caller_subadmin",
-        "get_subadmin",
     ],
     changeMethods: [
         "change_campground_fee",
         "change_campground_treasury_address",
         "change_campground_minimum_fee",
-        "add_setting",
+        "change_admin_change_delay",
+        "execute_admin_change",
+        "cancel_admin_change",
+        "propose_owner",
+        "accept_ownership",
+        "cancel_ownership_proposal",
         "nft_approve",
         "nft_revoke",
         "nft_revoke_all",
+        "set_config",
+        "remove_config",
         "create_trail_series",
+        "update_trail_series_metadata",
+        "freeze_trail_series",
+        "update_trail_series_supply",
+        "update_trail_series_price",
+        "close_trail_series_sale",
+        "delete_trail_series",
+        "archive_trail_series",
+        "unarchive_trail_series",
         "nft_buy_series",
         "nft_mint",
+        "migrate_v4_batch",
         "nft_transfer",
         "nft_transfer_call",
+        "pause",
+        "unpause",
+        "grant_role",
+        "revoke_role",
         "nft_transfer_payout",
         "remove_trail_from_nonmintable_list",
         "insert_trail_from_nonmintable_list",
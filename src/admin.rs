@@ -1,47 +1,258 @@
+use crate::event::NearEvent;
 use crate::*;
 
+/// A fee or treasury change waiting for its timelock to expire.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AdminChange {
+    /// New Campground percentage `fee`.
+    Fee(u64),
+    /// New treasury address.
+    TreasuryAddress(AccountId),
+    /// New Campground minimum `fee`, in yoctoNEAR.
+    MinimumFee(U128),
+    /// New delay for admin changes, in nanoseconds.
+    Delay(u64),
+}
+
+/// An `AdminChange` queued by `change_*` methods.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingAdminChange {
+    /// Identifies the change for `execute_admin_change` and `cancel_admin_change`.
+    pub change_id: u64,
+    /// The change to apply.
+    pub change: AdminChange,
+    /// Account that queued the change.
+    pub proposed_by: AccountId,
+    /// Block timestamp, in nanoseconds, from which the change can be executed.
+    pub executable_at: u64,
+}
+
 /// Provides admin operations to change different configurations of
 /// the contract.
 ///
-/// The contract `owner` is the only account allowed to perform these operations.
+/// Fee and treasury changes are allowed to the contract `owner`,
+/// any `Admin` and any `FinanceManager`.
+/// These changes are queued and can only be executed after `admin_change_delay`,
+/// giving creators notice. The contract `owner` can cancel them before execution.
+/// Ownership can only be transferred by the contract `owner`,
+/// and must be accepted by the proposed account.
 /// Otherwise, these operations panic.
+///
+/// Every change requires exactly 1 yoctoNEAR attached,
+/// so it cannot be signed with a function-call access key.
 pub trait AdminBridge {
-    /// Changes Campground percentage `fee`.
+    /// Queues a change of Campground percentage `fee`, from 0 to 100.
     /// When a creator creates a trail series,
     /// the series takes this `fee` as a default `fee`.
-    fn change_campground_fee(&mut self, fee: u64);
+    /// Returns the id of the queued change.
+    fn change_campground_fee(&mut self, fee: u64) -> u64;
 
-    /// Changes treasury address to a new one.
+    /// Queues a change of treasury address to a new one.
     /// The treasury address receives the applied `fee` after an NFT
     /// has been bought.
-    fn change_campground_treasury_address(&mut self, addr: AccountId);
+    /// Returns the id of the queued change.
+    fn change_campground_treasury_address(&mut self, addr: AccountId) -> u64;
+
+    /// Queues a change of campground minimum `fee`, in yoctoNEAR,
+    /// up to `MAX_CAMPGROUND_MINIMUM_FEE`.
+    /// Returns the id of the queued change.
+    fn change_campground_minimum_fee(&mut self, fee: Balance) -> u64;
+
+    /// Queues a change of the delay, in nanoseconds, applied to admin changes,
+    /// up to `MAX_ADMIN_CHANGE_DELAY`.
+    /// Only the contract `owner` and any `Admin` can change the delay.
+    /// Returns the id of the queued change.
+    fn change_admin_change_delay(&mut self, delay: u64) -> u64;
+
+    /// Applies a queued change once its timelock has expired.
+    /// Can be called by any account allowed to queue that change.
+    fn execute_admin_change(&mut self, change_id: u64);
+
+    /// Discards a queued change before it is executed.
+    fn cancel_admin_change(&mut self, change_id: u64);
+
+    /// Returns all the changes waiting to be executed.
+    fn get_pending_admin_changes(&self) -> Vec<PendingAdminChange>;
+
+    /// Returns the delay, in nanoseconds, applied to admin changes.
+    fn get_admin_change_delay(&self) -> u64;
 
-    /// Changes campground minimum `fee`, in yoctoNEAR.
-    fn change_campground_minimum_fee(&mut self, fee: Balance);
+    /// Proposes `new_owner_id` as the new contract `owner`.
+    /// Ownership is not transferred until `new_owner_id` calls `accept_ownership`.
+    /// A new proposal replaces any pending one.
+    fn propose_owner(&mut self, new_owner_id: AccountId);
 
-    /// Adds a setting key-val to the map
-    fn add_setting(&mut self, key: String, value: String);
+    /// Makes the caller the contract `owner`.
+    /// Only the account proposed by `propose_owner` can accept the ownership.
+    fn accept_ownership(&mut self);
+
+    /// Cancels the pending ownership transfer.
+    fn cancel_ownership_proposal(&mut self);
+
+    /// Returns the account proposed as the new contract `owner`, if any.
+    fn get_pending_owner(&self) -> Option<AccountId>;
 }
 
 #[near_bindgen]
 impl AdminBridge for Contract {
-    fn change_campground_fee(&mut self, fee: u64) {
-        self.panic_if_not_owner();
-        self.campground_fee = fee;
+    #[payable]
+    fn change_campground_fee(&mut self, fee: u64) -> u64 {
+        assert_one_yocto();
+        self.internal_queue_admin_change(AdminChange::Fee(fee))
+    }
+
+    #[payable]
+    fn change_campground_treasury_address(&mut self, addr: AccountId) -> u64 {
+        assert_one_yocto();
+        self.internal_queue_admin_change(AdminChange::TreasuryAddress(addr))
+    }
+
+    #[payable]
+    fn change_campground_minimum_fee(&mut self, fee: Balance) -> u64 {
+        assert_one_yocto();
+        self.internal_queue_admin_change(AdminChange::MinimumFee(fee.into()))
+    }
+
+    #[payable]
+    fn change_admin_change_delay(&mut self, delay: u64) -> u64 {
+        assert_one_yocto();
+        self.internal_queue_admin_change(AdminChange::Delay(delay))
+    }
+
+    #[payable]
+    fn execute_admin_change(&mut self, change_id: u64) {
+        assert_one_yocto();
+        let pending = self
+            .pending_admin_changes
+            .get(&change_id)
+            .unwrap_or_else(|| CampgroundError::AdminChangeNotFound.panic());
+        self.panic_if_cannot_change(&pending.change);
+        require(
+            env::block_timestamp() >= pending.executable_at,
+            CampgroundError::AdminChangeTimelocked,
+        );
+
+        self.pending_admin_changes.remove(&change_id);
+        match pending.change.clone() {
+            AdminChange::Fee(fee) => self.campground_fee = fee,
+            AdminChange::TreasuryAddress(addr) => self.campground_treasury_address = addr,
+            AdminChange::MinimumFee(fee) => self.campground_minimum_fee_yocto_near = fee.0,
+            AdminChange::Delay(delay) => self.admin_change_delay = delay,
+        }
+        NearEvent::log_admin_change_execute(change_id, pending.change, pending.executable_at);
     }
 
-    fn change_campground_treasury_address(&mut self, addr: AccountId) {
+    #[payable]
+    fn cancel_admin_change(&mut self, change_id: u64) {
+        assert_one_yocto();
         self.panic_if_not_owner();
-        self.campground_treasury_address = addr;
+        let pending = self
+            .pending_admin_changes
+            .remove(&change_id)
+            .unwrap_or_else(|| CampgroundError::AdminChangeNotFound.panic());
+
+        NearEvent::log_admin_change_cancel(change_id, pending.change, pending.executable_at);
     }
 
-    fn change_campground_minimum_fee(&mut self, fee: Balance) {
+    fn get_pending_admin_changes(&self) -> Vec<PendingAdminChange> {
+        self.pending_admin_changes.values().collect()
+    }
+
+    fn get_admin_change_delay(&self) -> u64 {
+        self.admin_change_delay
+    }
+
+    #[payable]
+    fn propose_owner(&mut self, new_owner_id: AccountId) {
+        assert_one_yocto();
         self.panic_if_not_owner();
-        self.campground_minimum_fee_yocto_near = fee
+        require(new_owner_id != self.owner_id, CampgroundError::AlreadyOwner);
+
+        NearEvent::log_ownership_transfer_propose(
+            self.owner_id.to_string(),
+            new_owner_id.to_string(),
+        );
+        self.pending_owner_id = Some(new_owner_id);
+    }
+
+    #[payable]
+    fn accept_ownership(&mut self) {
+        assert_one_yocto();
+        let new_owner_id = env::predecessor_account_id();
+        require(
+            self.pending_owner_id.as_ref() == Some(&new_owner_id),
+            CampgroundError::NotProposedOwner,
+        );
+
+        NearEvent::log_ownership_transfer_accept(
+            self.owner_id.to_string(),
+            new_owner_id.to_string(),
+        );
+        self.owner_id = new_owner_id;
+        self.pending_owner_id = None;
     }
 
-    fn add_setting(&mut self, key: String, value: String) {
+    #[payable]
+    fn cancel_ownership_proposal(&mut self) {
+        assert_one_yocto();
         self.panic_if_not_owner();
-        self.settings.insert(&key, &value);
+        let pending_owner_id = self
+            .pending_owner_id
+            .take()
+            .unwrap_or_else(|| CampgroundError::NoPendingOwner.panic());
+
+        NearEvent::log_ownership_transfer_cancel(
+            self.owner_id.to_string(),
+            pending_owner_id.to_string(),
+        );
+    }
+
+    fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
+
+impl Contract {
+    //validate a change and queue it until `admin_change_delay` has passed
+    fn internal_queue_admin_change(&mut self, change: AdminChange) -> u64 {
+        self.panic_if_cannot_change(&change);
+        match &change {
+            AdminChange::Fee(fee) => require(*fee <= 100, CampgroundError::FeeOutOfRange),
+            AdminChange::MinimumFee(fee) => require(
+                fee.0 <= MAX_CAMPGROUND_MINIMUM_FEE,
+                CampgroundError::MinimumFeeTooHigh,
+            ),
+            AdminChange::Delay(delay) => require(
+                *delay <= MAX_ADMIN_CHANGE_DELAY,
+                CampgroundError::DelayTooLong,
+            ),
+            AdminChange::TreasuryAddress(_) => {}
+        }
+
+        self.last_admin_change_id += 1;
+        let change_id = self.last_admin_change_id;
+        let executable_at = env::block_timestamp() + self.admin_change_delay;
+        self.pending_admin_changes.insert(
+            &change_id,
+            &PendingAdminChange {
+                change_id,
+                change: change.clone(),
+                proposed_by: env::predecessor_account_id(),
+                executable_at,
+            },
+        );
+
+        NearEvent::log_admin_change_queue(change_id, change, executable_at);
+        change_id
+    }
+
+    fn panic_if_cannot_change(&self, change: &AdminChange) {
+        if let AdminChange::Delay(_) = change {
+            self.panic_if_not_admin();
+        } else {
+            self.panic_if_not_finance_manager();
+        }
     }
 }
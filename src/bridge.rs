@@ -1,6 +1,6 @@
-use crate::vars::WHITELISTED_ADDRESS_MINTING_KEY;
+use crate::internal::paginate;
 use crate::*;
-use std::convert::TryFrom;
+use near_sdk::collections::Vector;
 
 pub trait SeriesBridge {
     /// Returns whether a trail is available in the smart contract.
@@ -18,18 +18,78 @@ pub trait SeriesBridge {
     /// Verifies whether a given user, `AccountId` owns the copy of a trail.
     fn is_owner(&self, series_id: &TrailId, owner_id: &AccountId) -> bool;
 
+    /// Returns how many copies of a trail a given user, `AccountId`, owns.
+    fn get_owned_copies_count(&self, series_id: &TrailId, owner_id: &AccountId) -> u64;
+
     /// Verifies whether a given user, `AccountId` is the creator of a given trail.
     fn is_creator(&self, series_id: &TrailId, owner_id: &AccountId) -> bool;
 
+    /// Returns a trail copy along with its trail series, `null` if not found.
+    /// Campground variant of `nft_token`.
+    fn trail_ticket(&self, token_id: TrailIdAndCopyNumber) -> Option<JsonTrail>;
+
     /// Returns the business information of a trail, `null` otherwise.
     fn get_trail_business(&self, trail_and_copy_id: &TrailIdAndCopyNumber)
         -> Option<TrailBusiness>;
 
     /// Returns all the trail copies owned by a given user, `AccountId`.
-    fn get_all_trails_by_owner(&self, owner_id: &AccountId) -> Vec<TrailSeries>;
+    /// Archived trails are left out unless `include_archived` is `true`.
+    fn get_all_trails_by_owner(
+        &self,
+        owner_id: &AccountId,
+        include_archived: Option<bool>,
+    ) -> Vec<TrailSeries>;
 
     /// Returns all the trails created by a given user (AccountId).
-    fn get_all_trails_by_creator(&self, creator_id: &AccountId) -> Vec<TrailSeries>;
+    /// Archived trails are left out unless `include_archived` is `true`.
+    fn get_all_trails_by_creator(
+        &self,
+        creator_id: &AccountId,
+        include_archived: Option<bool>,
+    ) -> Vec<TrailSeries>;
+
+    /// Returns up to `limit` trails (50 by default) starting at `from_index`.
+    /// Trails are listed in creation order, except that deleting a trail
+    /// moves the last listed one into its place.
+    /// Archived trails are left out unless `include_archived` is `true`.
+    /// `from_index` is a position among the trails listed, so a page shorter than `limit`
+    /// is the last one. Leaving archived trails out costs gas linear in `from_index`.
+    fn get_trails(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries>;
+
+    /// Returns the number of trails, including archived ones.
+    fn get_trails_count(&self) -> u64;
+
+    /// Paginated version of `get_all_trails_by_creator`, see `get_trails`.
+    fn get_trails_by_creator(
+        &self,
+        creator_id: &AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries>;
+
+    /// Returns the number of trails created by `creator_id`, including archived ones.
+    fn get_trails_count_by_creator(&self, creator_id: &AccountId) -> u64;
+
+    /// Paginated version of `get_all_trails_by_owner`, see `get_trails`.
+    /// Each trail is listed once, however many copies `owner_id` holds,
+    /// in the order first acquired. Giving away the last copy of a trail
+    /// moves the last listed one into its place.
+    fn get_trails_by_owner(
+        &self,
+        owner_id: &AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries>;
+
+    /// Returns the number of trails `owner_id` holds copies of, including archived ones.
+    fn get_trails_count_by_owner(&self, owner_id: &AccountId) -> u64;
 
     /// Returns the current minimum fee in YoctoNEAR by campground.
     fn get_current_fee(&self) -> U128;
@@ -46,11 +106,11 @@ pub trait SeriesBridge {
     /// Whether a trail can be minted by the user or not (for fiat/near purposes)
     fn is_trail_mintable(&self, trail_id: &TrailId) -> bool;
 
-    /// Gets the address of a whitelisted address
-    fn get_whitelisted_address(&self) -> AccountId;
+    /// Whether the metadata of a trail has been permanently frozen by its creator.
+    fn is_trail_frozen(&self, trail_id: &TrailId) -> bool;
 
-    /// Verifies there is a whitelisted address
-    fn is_there_whitelisted_address(&self) -> bool;
+    /// Whether a trail has been archived by its creator.
+    fn is_trail_archived(&self, trail_id: &TrailId) -> bool;
 }
 
 #[near_bindgen]
@@ -64,31 +124,23 @@ impl SeriesBridge for Contract {
     }
 
     fn get_trail_by_id_optional(&self, series_id: &TrailId) -> Option<TrailSeries> {
-        let token_series = self.trails_metadata_by_id.get(series_id);
-
-        token_series
+        self.internal_get_trail(series_id)
     }
 
     fn get_trail_by_id(&self, series_id: &TrailId) -> TrailSeries {
-        let token_series = self
-            .trails_metadata_by_id
-            .get(series_id)
-            .expect("Campground: Trail does not exist");
+        self.internal_get_trail(series_id)
+            .unwrap_or_else(|| CampgroundError::TrailNotFound.panic())
+    }
 
-        token_series
+    fn is_owner(&self, series_id: &TrailId, owner_id: &AccountId) -> bool {
+        self.get_owned_copies_count(series_id, owner_id) > 0
     }
 
-    fn is_owner(&self, series_id: &TrailIdAndCopyNumber, owner_id: &AccountId) -> bool {
-        let maybe_trails_owner = self.tokens_per_owner.get(owner_id);
-        if let Some(trails_owner) = maybe_trails_owner {
-            trails_owner.iter().any(|trail_id_and_copy_id| {
-                let id_and_copy: Vec<&str> = trail_id_and_copy_id.split(TRAIL_DELIMETER).collect();
-                let _series_id = id_and_copy.get(0).unwrap().clone();
-                _series_id == series_id
-            })
-        } else {
-            false
-        }
+    fn get_owned_copies_count(&self, series_id: &TrailId, owner_id: &AccountId) -> u64 {
+        self.holders_per_series
+            .get(series_id)
+            .and_then(|holders| holders.get(owner_id))
+            .unwrap_or(0)
     }
 
     fn is_creator(&self, series_id: &TrailId, owner_id: &AccountId) -> bool {
@@ -99,6 +151,34 @@ impl SeriesBridge for Contract {
         }
     }
 
+    fn trail_ticket(&self, token_id: TrailIdAndCopyNumber) -> Option<JsonTrail> {
+        //if there is some token ID in the tokens_by_id collection
+        if let Some(token) = self.tokens_by_id.get(&token_id) {
+            //we'll get the metadata for that token
+            let serie = self.internal_get_trail(&token.token_id).unwrap();
+            //we return the JsonTrail (wrapped by Some since we return an option)
+
+            // The metadata is derived from the series,
+            // format_json_trail then adds the NFT (Trail) numeration
+            let metadata = self.internal_token_metadata(
+                &serie,
+                token.issued_at,
+                self.trails_updated_at.get(&token.token_id),
+            );
+
+            Some(format_json_trail(
+                token_id,
+                token.owner_id,
+                serie,
+                metadata,
+                true,
+            ))
+        } else {
+            //if there wasn't a token ID in the tokens_by_id collection, we return None
+            None
+        }
+    }
+
     fn get_trail_business(
         &self,
         trail_and_copy_id: &TrailIdAndCopyNumber,
@@ -106,47 +186,106 @@ impl SeriesBridge for Contract {
         self.tokens_by_id.get(trail_and_copy_id)
     }
 
-    fn get_all_trails_by_owner(&self, owner_id: &AccountId) -> Vec<TrailSeries> {
-        let maybe_trails_owner = self.tokens_per_owner.get(owner_id);
+    fn get_all_trails_by_owner(
+        &self,
+        owner_id: &AccountId,
+        include_archived: Option<bool>,
+    ) -> Vec<TrailSeries> {
+        self.series_per_owner
+            .get(owner_id)
+            .map_or(vec![], |series| {
+                series
+                    .keys()
+                    .map(|id| self.get_trail_by_id(&id))
+                    .filter(|trail| include_archived.unwrap_or(false) || !trail.is_archived)
+                    .collect::<Vec<TrailSeries>>()
+            })
+    }
 
-        if let Some(trails_owner) = maybe_trails_owner {
-            let mut ids: Vec<String> = vec![];
-            trails_owner.iter().for_each(|trail_id_and_copy_id| {
-                let id_and_copy: Vec<&str> = trail_id_and_copy_id.split(TRAIL_DELIMETER).collect();
-                let _series_id = id_and_copy.get(0).unwrap().clone();
-                let _series_id_str = _series_id.to_string();
-                if !(ids.contains(&_series_id_str)) {
-                    ids.push(_series_id_str)
-                }
-            });
+    fn get_all_trails_by_creator(
+        &self,
+        creator_id: &AccountId,
+        include_archived: Option<bool>,
+    ) -> Vec<TrailSeries> {
+        let maybe_trails_owner = self.trails_series_by_creator.get(creator_id);
 
-            ids.iter()
-                .map(|id| self.get_trail_by_id(id))
+        if let Some(trails_owner) = maybe_trails_owner {
+            trails_owner
+                .iter()
+                .map(|id| self.get_trail_by_id(&id))
+                .filter(|trail| include_archived.unwrap_or(false) || !trail.is_archived)
                 .collect::<Vec<TrailSeries>>()
         } else {
             vec![]
         }
     }
 
-    fn get_all_trails_by_creator(&self, creator_id: &AccountId) -> Vec<TrailSeries> {
-        let maybe_trails_owner = self.trails_series_by_creator.get(creator_id);
+    fn get_trails(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries> {
+        self.internal_json_trail_series(
+            self.trails_metadata_by_id.keys_as_vector(),
+            from_index,
+            limit,
+            include_archived,
+        )
+    }
 
-        if let Some(trails_owner) = maybe_trails_owner {
-            let mut ids: Vec<String> = vec![];
-            trails_owner.iter().for_each(|trail_id| {
-                if !(ids.contains(&trail_id)) {
-                    ids.push(trail_id)
-                }
-            });
+    fn get_trails_count(&self) -> u64 {
+        self.trails_metadata_by_id.len()
+    }
 
-            ids.iter()
-                .map(|id| self.get_trail_by_id(id))
-                .collect::<Vec<TrailSeries>>()
-        } else {
-            vec![]
+    fn get_trails_by_creator(
+        &self,
+        creator_id: &AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries> {
+        match self.trails_series_by_creator.get(creator_id) {
+            Some(trails) => self.internal_json_trail_series(
+                trails.as_vector(),
+                from_index,
+                limit,
+                include_archived,
+            ),
+            None => vec![],
+        }
+    }
+
+    fn get_trails_count_by_creator(&self, creator_id: &AccountId) -> u64 {
+        self.trails_series_by_creator
+            .get(creator_id)
+            .map_or(0, |trails| trails.len())
+    }
+
+    fn get_trails_by_owner(
+        &self,
+        owner_id: &AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries> {
+        match self.series_per_owner.get(owner_id) {
+            Some(series) => self.internal_json_trail_series(
+                series.keys_as_vector(),
+                from_index,
+                limit,
+                include_archived,
+            ),
+            None => vec![],
         }
     }
 
+    fn get_trails_count_by_owner(&self, owner_id: &AccountId) -> u64 {
+        self.series_per_owner
+            .get(owner_id)
+            .map_or(0, |series| series.len())
+    }
+
     fn get_current_fee(&self) -> U128 {
         U128(self.campground_minimum_fee_yocto_near)
     }
@@ -168,15 +307,44 @@ impl SeriesBridge for Contract {
         !self.nonmintable_trails.contains(trail_id)
     }
 
-    fn is_there_whitelisted_address(&self) -> bool {
-        let key = String::from(WHITELISTED_ADDRESS_MINTING_KEY);
-        self.settings.get(&key).is_some()
+    fn is_trail_frozen(&self, trail_id: &TrailId) -> bool {
+        self.frozen_trails.contains(trail_id)
     }
 
-    fn get_whitelisted_address(&self) -> AccountId {
-        let key = String::from(WHITELISTED_ADDRESS_MINTING_KEY);
-        let value = self.settings.get(&key).unwrap_or_else(|| String::new());
-        let acct_id: AccountId = AccountId::try_from(value).unwrap();
-        acct_id
+    fn is_trail_archived(&self, trail_id: &TrailId) -> bool {
+        self.archived_trails.contains(trail_id)
+    }
+}
+
+impl Contract {
+    /// Archived trails are filtered out before taking `limit`,
+    /// so that a page is only short once there are no trails left.
+    fn internal_json_trail_series(
+        &self,
+        ids: &Vector<TrailId>,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        include_archived: Option<bool>,
+    ) -> Vec<JsonTrailSeries> {
+        let page: Vec<TrailId> = if include_archived.unwrap_or(false) {
+            paginate(ids.len(), from_index, limit)
+                .filter_map(|index| ids.get(index))
+                .collect()
+        } else {
+            //capped to the number of trails, so that it fits in a usize
+            let start = from_index.map_or(0, |index| index.0).min(ids.len() as u128) as usize;
+            ids.iter()
+                .filter(|series_id| !self.archived_trails.contains(series_id))
+                .skip(start)
+                .take(limit.unwrap_or(50) as usize)
+                .collect()
+        };
+
+        page.into_iter()
+            .map(|series_id| JsonTrailSeries {
+                series: self.get_trail_by_id(&series_id),
+                series_id,
+            })
+            .collect()
     }
 }
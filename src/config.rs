@@ -0,0 +1,219 @@
+use crate::event::NearEvent;
+use crate::*;
+
+/// Known configuration keys of the contract.
+///
+/// These keys are new, not migrated from the free-form `settings` used up to `v3`.
+/// Those settings only held the sub-admin and fiat minter accounts,
+/// which are migrated to the `SubAdmin` and `FiatMinter` roles instead.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ConfigKey {
+    /// Trails must be priced lower than this amount, in yoctoNEAR.
+    MaxPrice,
+    /// Media shown for trails without their own `media` nor `fallback_media`.
+    FallbackMedia,
+    /// Maximum length in bytes of trail and resource titles.
+    MaxTitleLength,
+    /// Maximum length in bytes of trail and resource descriptions.
+    MaxDescriptionLength,
+    /// Maximum length in bytes of the trail `data` and resource `extra`.
+    MaxExtraLength,
+    /// Maximum length in bytes of every `media` and `reference` URI.
+    MaxUriLength,
+    /// Maximum number of resources per trail.
+    MaxResources,
+}
+
+impl ConfigKey {
+    /// Every known key, in listing order.
+    pub const ALL: [ConfigKey; 7] = [
+        ConfigKey::MaxPrice,
+        ConfigKey::FallbackMedia,
+        ConfigKey::MaxTitleLength,
+        ConfigKey::MaxDescriptionLength,
+        ConfigKey::MaxExtraLength,
+        ConfigKey::MaxUriLength,
+        ConfigKey::MaxResources,
+    ];
+
+    /// Value used while the key has not been set.
+    pub fn default_entry(self) -> ConfigEntry {
+        Config::default().get(self)
+    }
+}
+
+/// Current value of every configuration key, stored inline in the contract state.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Config {
+    pub max_price: Balance,
+    pub fallback_media: Option<String>,
+    pub max_title_length: u32,
+    pub max_description_length: u32,
+    pub max_extra_length: u32,
+    pub max_uri_length: u32,
+    pub max_resources: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_price: MAX_PRICE,
+            fallback_media: Some(DEFAULT_FALLBACK_MEDIA.to_string()),
+            max_title_length: 256,
+            max_description_length: 4096,
+            max_extra_length: 8192,
+            max_uri_length: 2048,
+            max_resources: 32,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the value of `key` as an entry.
+    pub fn get(&self, key: ConfigKey) -> ConfigEntry {
+        match key {
+            ConfigKey::MaxPrice => ConfigEntry::MaxPrice(self.max_price.into()),
+            ConfigKey::FallbackMedia => ConfigEntry::FallbackMedia(self.fallback_media.clone()),
+            ConfigKey::MaxTitleLength => ConfigEntry::MaxTitleLength(self.max_title_length),
+            ConfigKey::MaxDescriptionLength => {
+                ConfigEntry::MaxDescriptionLength(self.max_description_length)
+            }
+            ConfigKey::MaxExtraLength => ConfigEntry::MaxExtraLength(self.max_extra_length),
+            ConfigKey::MaxUriLength => ConfigEntry::MaxUriLength(self.max_uri_length),
+            ConfigKey::MaxResources => ConfigEntry::MaxResources(self.max_resources),
+        }
+    }
+
+    /// Stores the value of `entry` under its key.
+    pub fn set(&mut self, entry: ConfigEntry) {
+        match entry {
+            ConfigEntry::MaxPrice(price) => self.max_price = price.0,
+            ConfigEntry::FallbackMedia(media) => self.fallback_media = media,
+            ConfigEntry::MaxTitleLength(limit) => self.max_title_length = limit,
+            ConfigEntry::MaxDescriptionLength(limit) => self.max_description_length = limit,
+            ConfigEntry::MaxExtraLength(limit) => self.max_extra_length = limit,
+            ConfigEntry::MaxUriLength(limit) => self.max_uri_length = limit,
+            ConfigEntry::MaxResources(limit) => self.max_resources = limit,
+        }
+    }
+}
+
+/// A configuration key along with its typed value.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ConfigEntry {
+    /// Must be greater than 0 and not higher than `MAX_PRICE`.
+    MaxPrice(U128),
+    /// Must not be empty, `None` leaves trails without media.
+    /// Relative paths are resolved against `base_uri`, otherwise
+    /// the scheme must be one of `ALLOWED_URI_SCHEMES`.
+    FallbackMedia(Option<String>),
+    /// Must be between 1 and `MAX_METADATA_LIMIT`, as every limit below.
+    MaxTitleLength(u32),
+    MaxDescriptionLength(u32),
+    MaxExtraLength(u32),
+    MaxUriLength(u32),
+    MaxResources(u32),
+}
+
+impl ConfigEntry {
+    /// Returns the key this entry is stored under.
+    pub fn key(&self) -> ConfigKey {
+        match self {
+            ConfigEntry::MaxPrice(_) => ConfigKey::MaxPrice,
+            ConfigEntry::FallbackMedia(_) => ConfigKey::FallbackMedia,
+            ConfigEntry::MaxTitleLength(_) => ConfigKey::MaxTitleLength,
+            ConfigEntry::MaxDescriptionLength(_) => ConfigKey::MaxDescriptionLength,
+            ConfigEntry::MaxExtraLength(_) => ConfigKey::MaxExtraLength,
+            ConfigEntry::MaxUriLength(_) => ConfigKey::MaxUriLength,
+            ConfigEntry::MaxResources(_) => ConfigKey::MaxResources,
+        }
+    }
+
+    /// Panics if the value is not valid for its key under the `current` configuration.
+    fn assert_valid(&self, current: &Config) {
+        match self {
+            ConfigEntry::MaxPrice(price) => require(
+                price.0 > 0 && price.0 <= MAX_PRICE,
+                CampgroundError::MaxPriceOutOfRange(MAX_PRICE),
+            ),
+            ConfigEntry::FallbackMedia(media) => {
+                if let Some(media) = media {
+                    require(!media.is_empty(), CampgroundError::EmptyFallbackMedia);
+                    // Checked as series `media`, as it is stored inline in the contract state
+                    assert_valid_uri("FallbackMedia", media, current.max_uri_length as usize);
+                }
+            }
+            ConfigEntry::MaxTitleLength(limit)
+            | ConfigEntry::MaxDescriptionLength(limit)
+            | ConfigEntry::MaxExtraLength(limit)
+            | ConfigEntry::MaxUriLength(limit)
+            | ConfigEntry::MaxResources(limit) => require(
+                *limit > 0 && *limit <= MAX_METADATA_LIMIT,
+                CampgroundError::LimitOutOfRange(self.key(), MAX_METADATA_LIMIT),
+            ),
+        }
+    }
+}
+
+/// Provides operations to manage the contract configuration.
+///
+/// Only the contract `owner` and any `Admin` can change the configuration,
+/// attaching exactly 1 yoctoNEAR.
+pub trait ConfigBridge {
+    /// Sets the value of a configuration key, validating it first.
+    fn set_config(&mut self, entry: ConfigEntry);
+
+    /// Restores a configuration key to its default value.
+    /// Returns `false` if the key already had its default value.
+    fn remove_config(&mut self, key: ConfigKey) -> bool;
+
+    /// Returns the current value of a configuration key.
+    fn get_config(&self, key: ConfigKey) -> ConfigEntry;
+
+    /// Returns the current value of every configuration key.
+    fn get_configs(&self) -> Vec<ConfigEntry>;
+}
+
+#[near_bindgen]
+impl ConfigBridge for Contract {
+    #[payable]
+    fn set_config(&mut self, entry: ConfigEntry) {
+        assert_one_yocto();
+        self.panic_if_not_admin();
+        entry.assert_valid(&self.config);
+        self.config.set(entry.clone());
+        NearEvent::log_config_update(
+            entry.key(),
+            Some(entry),
+            env::predecessor_account_id().to_string(),
+        );
+    }
+
+    #[payable]
+    fn remove_config(&mut self, key: ConfigKey) -> bool {
+        assert_one_yocto();
+        self.panic_if_not_admin();
+        let default = key.default_entry();
+        let removed = self.config.get(key) != default;
+        if removed {
+            self.config.set(default);
+            NearEvent::log_config_update(key, None, env::predecessor_account_id().to_string());
+        }
+        removed
+    }
+
+    fn get_config(&self, key: ConfigKey) -> ConfigEntry {
+        self.config.get(key)
+    }
+
+    fn get_configs(&self) -> Vec<ConfigEntry> {
+        ConfigKey::ALL
+            .iter()
+            .map(|key| self.get_config(*key))
+            .collect()
+    }
+}
@@ -1,10 +1,13 @@
 use crate::bridge::SeriesBridge;
+use crate::event::NearEvent;
+use crate::pause::PausableOperation;
 use crate::*;
-use std::convert::TryFrom;
 
 /// Provides operations to deal with trail series.
 pub trait CreateTrailSeries {
     /// Creates a series (trail) inside the smart contract.
+    /// The trail is identified by `slug` when given,
+    /// otherwise by the next number in the contract counter.
     fn create_trail_series(
         &mut self,
         metadata: TrailSeriesMetadata,
@@ -12,8 +15,13 @@ pub trait CreateTrailSeries {
         creator: Option<AccountId>,
         creator_royalty: Option<U128>,
         allow_user_minting: Option<bool>,
+        slug: Option<TrailId>,
     ) -> JsonTrail;
 
+    /// Returns the deposit `create_trail_series` needs for its storage, with the same arguments.
+    /// It is exact when `creator_id` is given, as views do not know their caller.
+    /// Otherwise it assumes a first-time creator with the longest account id,
+    /// and `create_trail_series` refunds the difference.
     fn create_trail_series_estimated(
         &self,
         metadata: TrailSeriesMetadata,
@@ -21,12 +29,54 @@ pub trait CreateTrailSeries {
         creator: Option<AccountId>,
         creator_royalty: Option<U128>,
         allow_user_minting: Option<bool>,
+        slug: Option<TrailId>,
     ) -> Option<U128>;
+
+    /// Replaces the metadata and resources of a trail.
+    /// Only the trail creator can update it, as long as it is not frozen.
+    /// `tickets_amount` is kept from the current metadata.
+    fn update_trail_series_metadata(&mut self, trail_id: TrailId, metadata: TrailSeriesMetadata);
+
+    /// Permanently freezes the metadata and resources of a trail.
+    /// Only the trail creator can freeze it, and it cannot be undone,
+    /// so it requires exactly 1 yoctoNEAR attached.
+    /// The storage recording the freeze is covered by the contract.
+    fn freeze_trail_series(&mut self, trail_id: TrailId);
+
+    /// Increases or reduces the `total` supply of a trail.
+    /// The new `total` cannot be lower than the circulating copies.
+    /// `supply.total` is authoritative: `metadata.tickets_amount` keeps the supply
+    /// the trail was created with, so frozen metadata is never rewritten.
+    /// Only the trail creator can change it.
+    fn update_trail_series_supply(&mut self, trail_id: TrailId, total: u64);
+
+    /// Changes the `price` of a trail, recalculating the Campground fee with
+    /// the current fee configuration. Copies already sold are not affected.
+    /// Only the trail creator can change it, as long as `UpdatePrice` is not paused.
+    /// Freezing a trail does not lock its price.
+    fn update_trail_series_price(&mut self, trail_id: TrailId, price: U128);
+
+    /// Closes the sale of a trail by reducing its `total` supply to the circulating copies.
+    /// The sale can be reopened later with `update_trail_series_supply`.
+    fn close_trail_series_sale(&mut self, trail_id: TrailId);
+
+    /// Deletes a trail without circulating copies,
+    /// refunding the storage it used to its creator.
+    /// Frozen trails can be deleted too, as no collector holds a copy yet.
+    fn delete_trail_series(&mut self, trail_id: TrailId);
+
+    /// Archives a trail, hiding it from listings.
+    /// Copies already minted are kept, but no more copies can be minted.
+    /// Can be called by the trail creator, the contract `owner`, any `Admin` or any `Moderator`.
+    fn archive_trail_series(&mut self, trail_id: TrailId);
+
+    /// Restores an archived trail back into listings.
+    /// Can be called by the trail creator, the contract `owner`, any `Admin` or any `Moderator`.
+    fn unarchive_trail_series(&mut self, trail_id: TrailId);
 }
 
 #[near_bindgen]
 impl CreateTrailSeries for Contract {
-    #[allow(unused_variables)]
     fn create_trail_series_estimated(
         &self,
         metadata: TrailSeriesMetadata,
@@ -34,17 +84,24 @@ impl CreateTrailSeries for Contract {
         creator_id: Option<AccountId>,
         creator_royalty: Option<U128>,
         allow_user_minting: Option<bool>,
+        slug: Option<TrailId>,
     ) -> Option<U128> {
-        let input_bytes = env::input().unwrap_or(vec![]).len();
-        let high_approximate = input_bytes + 500;
-        let usize_to_u128 = u128::try_from(high_approximate);
-        if usize_to_u128.is_ok() {
-            Some(U128(
-                (usize_to_u128.unwrap() * env::storage_byte_cost()).into(),
-            ))
-        } else {
-            None
-        }
+        let creator_id =
+            creator_id.unwrap_or_else(|| "a".repeat(MAX_ACCOUNT_ID_LENGTH).parse().unwrap());
+        let trail_id = slug.unwrap_or_else(|| (self.last_trail_id + 1).to_string());
+        let trail_series = self.internal_new_trail_series(
+            creator_id,
+            metadata,
+            price.map_or(0, |price| price.0),
+            creator_royalty,
+        );
+
+        let bytes = self.internal_create_storage(
+            &trail_id,
+            &trail_series,
+            allow_user_minting.unwrap_or(true),
+        );
+        Some(U128(Balance::from(bytes) * env::storage_byte_cost()))
     }
 
     #[payable]
@@ -55,68 +112,49 @@ impl CreateTrailSeries for Contract {
         creator_id: Option<AccountId>,
         creator_royalty: Option<U128>,
         allow_user_minting: Option<bool>,
+        slug: Option<TrailId>,
     ) -> JsonTrail {
+        self.panic_if_paused(PausableOperation::Create);
+
         let initial_storage_usage = env::storage_usage();
         let creator_id = creator_id.unwrap_or(env::predecessor_account_id());
-        let current_block_timestamp = env::block_timestamp();
-        let token_series_id = format!("{}", (self.trails_metadata_by_id.len() + 1));
+        let token_series_id = match slug {
+            Some(slug) => {
+                assert_valid_slug(&slug);
+                slug
+            }
+            None => self.next_trail_id(),
+        };
 
-        assert!(
+        require(
             !(self.series_exists(&token_series_id)),
-            "Campground: Duplicate series id"
+            CampgroundError::DuplicateTrailId,
         );
 
-        let maybe_price = price.clone();
-
-        let price_res: Option<u128> = if maybe_price.is_some() {
-            let indicated_price = maybe_price.unwrap().0;
-            assert!(
-                indicated_price < MAX_PRICE,
-                "Campground: price higher than {}",
-                MAX_PRICE
+        let max_price = self.config.max_price;
+        let price_res: Option<u128> = price.map(|indicated_price| {
+            let indicated_price = indicated_price.0;
+            require(
+                indicated_price < max_price,
+                CampgroundError::PriceTooHigh(max_price),
             );
-            Some(indicated_price)
-        } else {
-            None
-        };
+            indicated_price
+        });
 
-        let quantity = metadata.tickets_amount;
-        assert!(
-            quantity > 0,
-            "Campground: At least 1 ticket is required per trail series"
-        );
+        require(metadata.tickets_amount > 0, CampgroundError::NoTickets);
 
-        let resources_len = metadata.resources.len();
-        assert!(
-            resources_len > 0,
-            "Campground: At least 1 resource is needed per trail"
-        );
+        self.assert_valid_series_metadata(&metadata);
 
         // let can_be_traded_at = metadata.starts_at.unwrap_or(current_block_timestamp.clone());
         // let valid_until = metadata.expires_at.unwrap_or_else(|| u64::MAX);
         // assert!(valid_until > can_be_traded_at, "Campground: Trail tickets need to be valid in a greater date than the start date");
 
-        let price = price_res.unwrap_or(0);
-        let campground_fee_near = U128(calculate_fee(
-            price,
-            self.campground_fee,
-            self.campground_minimum_fee_yocto_near,
-        ));
-
-        let trail_series = TrailSeries {
-            is_mintable: true,
-            creator_id: creator_id.clone(),
-            issue_at: current_block_timestamp,
+        let trail_series = self.internal_new_trail_series(
+            creator_id.clone(),
             metadata,
-            supply: SeriesSupply {
-                total: quantity,
-                circulating: 0 as u64,
-            },
-            price: price.into(),
-            campground_fee_near,
-            creator_royalty_near: creator_royalty,
-            royalties: HashMap::new(),
-        };
+            price_res.unwrap_or(0),
+            creator_royalty,
+        );
 
         self.trails_metadata_by_id
             .insert(&token_series_id, &trail_series);
@@ -128,14 +166,314 @@ impl CreateTrailSeries for Contract {
             self.add_nonmintable_trail(&token_series_id);
         }
 
+        NearEvent::log_series_create(
+            token_series_id.clone(),
+            creator_id.to_string(),
+            trail_series.price,
+            trail_series.supply.total,
+            is_mintable,
+        );
+
         refund_deposit(env::storage_usage() - initial_storage_usage, 0);
 
         format_json_trail(
             token_series_id,
             creator_id,
             trail_series.clone(),
-            partial_metadata_from_trail_series(&trail_series),
+            self.internal_token_metadata(&trail_series, None, None),
             false,
         )
     }
+
+    #[payable]
+    fn update_trail_series_metadata(&mut self, trail_id: TrailId, metadata: TrailSeriesMetadata) {
+        let initial_storage_usage = env::storage_usage();
+        let mut trail = self.get_trail_by_id(&trail_id);
+
+        require(
+            trail.creator_id == env::predecessor_account_id(),
+            CampgroundError::NotTrailCreator("update its metadata"),
+        );
+        self.panic_if_trail_frozen(&trail_id);
+
+        self.assert_valid_series_metadata(&metadata);
+
+        let tickets_amount = trail.metadata.tickets_amount;
+        trail.metadata = TrailSeriesMetadata {
+            tickets_amount,
+            ..metadata
+        };
+        self.trails_metadata_by_id.insert(&trail_id, &trail);
+        let updated_at = env::block_timestamp() / 1_000_000;
+        self.trails_updated_at.insert(&trail_id, &updated_at);
+
+        refund_deposit(
+            env::storage_usage().saturating_sub(initial_storage_usage),
+            0,
+        );
+
+        NearEvent::log_series_metadata_update(trail_id, trail.creator_id.to_string(), updated_at);
+    }
+
+    #[payable]
+    fn freeze_trail_series(&mut self, trail_id: TrailId) {
+        assert_one_yocto();
+        let trail = self.get_trail_by_id(&trail_id);
+
+        require(
+            trail.creator_id == env::predecessor_account_id(),
+            CampgroundError::NotTrailCreator("freeze its metadata"),
+        );
+        require(
+            self.frozen_trails.insert(&trail_id),
+            CampgroundError::TrailAlreadyFrozen,
+        );
+
+        NearEvent::log_series_freeze(trail_id, trail.creator_id.to_string());
+    }
+
+    #[payable]
+    fn update_trail_series_supply(&mut self, trail_id: TrailId, total: u64) {
+        assert_one_yocto();
+        let (previous_total, trail) = self.internal_set_trail_supply(&trail_id, total);
+
+        NearEvent::log_series_supply_update(
+            trail_id,
+            previous_total,
+            trail.supply.total,
+            trail.supply.circulating,
+        );
+    }
+
+    #[payable]
+    fn update_trail_series_price(&mut self, trail_id: TrailId, price: U128) {
+        assert_one_yocto();
+        self.panic_if_paused(PausableOperation::UpdatePrice);
+        let mut trail = self.get_trail_by_id(&trail_id);
+        require(
+            trail.creator_id == env::predecessor_account_id(),
+            CampgroundError::NotTrailCreator("change its price"),
+        );
+        let max_price = self.config.max_price;
+        require(
+            price.0 < max_price,
+            CampgroundError::PriceTooHigh(max_price),
+        );
+
+        let previous_price = trail.price;
+        trail.price = price;
+        trail.campground_fee_near = U128(calculate_fee(
+            price.0,
+            self.campground_fee,
+            self.campground_minimum_fee_yocto_near,
+        ));
+        self.trails_metadata_by_id.insert(&trail_id, &trail);
+
+        NearEvent::log_series_price_update(trail_id, previous_price, price);
+    }
+
+    #[payable]
+    fn close_trail_series_sale(&mut self, trail_id: TrailId) {
+        assert_one_yocto();
+        let circulating = self.get_trail_by_id(&trail_id).supply.circulating;
+        let (previous_total, trail) = self.internal_set_trail_supply(&trail_id, circulating);
+
+        NearEvent::log_series_sale_close(
+            trail_id,
+            previous_total,
+            trail.supply.total,
+            trail.supply.circulating,
+        );
+    }
+
+    #[payable]
+    fn delete_trail_series(&mut self, trail_id: TrailId) {
+        assert_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+        let trail = self.get_trail_by_id(&trail_id);
+
+        require(
+            trail.creator_id == env::predecessor_account_id(),
+            CampgroundError::NotTrailCreator("delete it"),
+        );
+        require(
+            trail.supply.circulating == 0,
+            CampgroundError::TrailHasCirculatingCopies,
+        );
+
+        self.trails_metadata_by_id.remove(&trail_id);
+        self.internal_remove_trail_from_creator(&trail.creator_id, &trail_id);
+        self.nonmintable_trails.remove(&trail_id);
+        self.archived_trails.remove(&trail_id);
+        self.trails_updated_at.remove(&trail_id);
+
+        let storage_released = initial_storage_usage - env::storage_usage();
+        if storage_released > 0 {
+            Promise::new(trail.creator_id)
+                .transfer(Balance::from(storage_released) * env::storage_byte_cost());
+        }
+
+        // Not refunded, as the contract covered the storage of the freeze
+        self.frozen_trails.remove(&trail_id);
+    }
+
+    #[payable]
+    fn archive_trail_series(&mut self, trail_id: TrailId) {
+        let initial_storage_usage = env::storage_usage();
+        let trail = self.get_trail_by_id(&trail_id);
+
+        if trail.creator_id != env::predecessor_account_id() {
+            self.panic_if_not_moderator();
+        }
+        require(
+            self.archived_trails.insert(&trail_id),
+            CampgroundError::TrailAlreadyArchived,
+        );
+
+        refund_deposit(env::storage_usage() - initial_storage_usage, 0);
+    }
+
+    fn unarchive_trail_series(&mut self, trail_id: TrailId) {
+        let trail = self.get_trail_by_id(&trail_id);
+
+        if trail.creator_id != env::predecessor_account_id() {
+            self.panic_if_not_moderator();
+        }
+        require(
+            self.archived_trails.remove(&trail_id),
+            CampgroundError::TrailNotArchived,
+        );
+    }
+}
+
+/// Panics if `slug` cannot be used as a `TrailId`.
+/// Numeric slugs are reserved for the contract counter.
+fn assert_valid_slug(slug: &str) {
+    require(
+        !slug.is_empty() && slug.len() <= MAX_SLUG_LENGTH,
+        CampgroundError::SlugLength,
+    );
+    require(
+        !slug.contains(TRAIL_DELIMETER),
+        CampgroundError::SlugDelimiter,
+    );
+    require(
+        slug.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        CampgroundError::SlugCharacters,
+    );
+    require(
+        !slug.chars().all(|c| c.is_ascii_digit()),
+        CampgroundError::SlugNumeric,
+    );
+}
+
+impl Contract {
+    /// Builds a trail series as `create_trail_series` stores it, with no copies minted yet.
+    pub(crate) fn internal_new_trail_series(
+        &self,
+        creator_id: AccountId,
+        metadata: TrailSeriesMetadata,
+        price: Balance,
+        creator_royalty: Option<U128>,
+    ) -> TrailSeries {
+        let campground_fee_near = U128(calculate_fee(
+            price,
+            self.campground_fee,
+            self.campground_minimum_fee_yocto_near,
+        ));
+
+        TrailSeries {
+            is_mintable: true,
+            creator_id,
+            issue_at: env::block_timestamp(),
+            supply: SeriesSupply {
+                total: metadata.tickets_amount,
+                circulating: 0,
+            },
+            metadata,
+            price: price.into(),
+            campground_fee_near,
+            creator_royalty_near: creator_royalty,
+            royalties: HashMap::new(),
+            is_frozen: false,
+            is_archived: false,
+        }
+    }
+
+    /// Panics if `metadata` exceeds any of the configured size limits,
+    /// or has an invalid URI, media hash or campground id.
+    /// Applies to every path storing series metadata.
+    fn assert_valid_series_metadata(&self, metadata: &TrailSeriesMetadata) {
+        let max_resources = self.config.max_resources as usize;
+        require(!metadata.resources.is_empty(), CampgroundError::NoResources);
+        require(
+            metadata.resources.len() <= max_resources,
+            CampgroundError::TooManyResources(max_resources),
+        );
+
+        let max_title = self.config.max_title_length as usize;
+        let max_description = self.config.max_description_length as usize;
+        let max_extra = self.config.max_extra_length as usize;
+        let max_uri = self.config.max_uri_length as usize;
+
+        assert_max_length("title", &metadata.title, max_title);
+        assert_max_length("description", &metadata.description, max_description);
+        if let Some(data) = &metadata.data {
+            assert_max_length("data", data, max_extra);
+        }
+        for (field, uri) in [
+            ("media", &metadata.media),
+            ("reference", &metadata.reference),
+            ("fallback_media", &metadata.fallback_media),
+        ] {
+            if let Some(uri) = uri {
+                assert_valid_uri(field, uri, max_uri);
+            }
+        }
+
+        for resource in &metadata.resources {
+            if let Some(title) = &resource.title {
+                assert_max_length("resource title", title, max_title);
+            }
+            if let Some(description) = &resource.description {
+                assert_max_length("resource description", description, max_description);
+            }
+            if let Some(extra) = &resource.extra {
+                assert_max_length("resource extra", extra, max_extra);
+            }
+            assert_valid_uri("resource media", &resource.media, max_uri);
+            if let Some(reference) = &resource.reference {
+                assert_valid_uri("resource reference", reference, max_uri);
+            }
+        }
+
+        assert_valid_media_hash(metadata);
+        assert_valid_campground_id(&metadata.campground_id);
+    }
+}
+
+/// Panics if `campground_id` is empty, too long or has characters other than
+/// ASCII letters, digits, `-` and `_`.
+fn assert_valid_campground_id(campground_id: &str) {
+    require(
+        !campground_id.is_empty()
+            && campground_id.len() <= MAX_CAMPGROUND_ID_LENGTH
+            && campground_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        CampgroundError::InvalidCampgroundId,
+    );
+}
+
+/// Panics if `metadata` has a `media_hash` without `media`, or one that is not SHA-256 sized.
+/// Base64 decoding is already checked when deserializing the arguments.
+fn assert_valid_media_hash(metadata: &TrailSeriesMetadata) {
+    if let Some(media_hash) = &metadata.media_hash {
+        require(
+            metadata.media.is_some(),
+            CampgroundError::MediaHashWithoutMedia,
+        );
+        require(media_hash.0.len() == 32, CampgroundError::InvalidMediaHash);
+    }
 }
@@ -1,3 +1,5 @@
+use crate::bridge::SeriesBridge;
+use crate::internal::paginate;
 use crate::*;
 
 /// NEP-181 interface definition.
@@ -7,14 +9,21 @@ pub trait NonFungibleTokenEnumeration {
     /// Query for the total supply of NFTs on the contract.
     fn nft_total_supply(&self) -> U128;
 
-    /// Query for nft tokens on the contract regardless of the owner using pagination.
-    fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTrail>;
+    /// Query for nft tokens on the contract regardless of the owner using pagination,
+    /// in mint order.
+    fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken>;
+
+    /// Campground variant of `nft_tokens`, along with the trail series of each token.
+    fn trail_tickets(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTrail>;
 
     /// Get the total supply of NFTs for a given owner.
     fn nft_supply_for_owner(&self, account_id: AccountId) -> U128;
 
-    /// Query all tokens of an owner.
+    /// Query up to `limit` tokens of an owner (50 by default), in mint order.
     /// Similar to get_all_trails_by_owner with pagination.
+    /// `from_index` is a position in the owner's own tokens, as per NEP-181.
+    /// Skipping to `from_index` costs gas linear in `from_index`,
+    /// prefer `trail_tickets_for_owner_after` to go through large collections.
     fn trail_tickets_for_owner(
         &self,
         account_id: AccountId,
@@ -22,13 +31,51 @@ pub trait NonFungibleTokenEnumeration {
         limit: Option<u64>,
     ) -> Vec<JsonTrail>;
 
-    /// Get list of all tokens owned by a given account
+    /// Query up to `limit` tokens of an owner (50 by default), in mint order,
+    /// starting right after the token `after`, or from the first token if not given.
+    /// Passing the last token of a page fetches the next page, at the same cost as the first one.
+    fn trail_tickets_for_owner_after(
+        &self,
+        account_id: AccountId,
+        after: Option<TrailIdAndCopyNumber>,
+        limit: Option<u64>,
+    ) -> Vec<JsonTrail>;
+
+    /// Get list of all tokens owned by a given account, paginated as `trail_tickets_for_owner`.
     fn nft_tokens_for_owner(
         &self,
         account_id: AccountId,
         from_index: Option<U128>,
         limit: Option<u64>,
+    ) -> Vec<JsonToken>;
+
+    /// Get the number of copies minted for a given trail series.
+    fn nft_supply_for_series(&self, series_id: TrailId) -> U128;
+
+    /// Query the copies minted for a trail series using pagination, in mint order.
+    fn nft_tokens_for_series(
+        &self,
+        series_id: TrailId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken>;
+
+    /// Campground variant of `nft_tokens_for_series`, along with the trail series.
+    fn trail_tickets_for_series(
+        &self,
+        series_id: TrailId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
     ) -> Vec<JsonTrail>;
+
+    /// Query the distinct holders of a trail series along with their copy counts,
+    /// using pagination.
+    fn nft_holders_for_series(
+        &self,
+        series_id: TrailId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<SeriesHolder>;
 }
 
 #[near_bindgen]
@@ -39,20 +86,22 @@ impl NonFungibleTokenEnumeration for Contract {
     }
 
     /// Query for nft tokens on the contract regardless of the owner using pagination.
-    fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTrail> {
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
+    fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
+        self.trail_tickets(from_index, limit)
+            .into_iter()
+            .map(JsonToken::from)
+            .collect()
+    }
 
-        //iterate through each token using an iterator
-        self.token_metadata_by_id
-            .keys()
-            //skip to the index we specified in the start variable
-            .skip(start as usize)
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.unwrap_or(50) as usize)
+    fn trail_tickets(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTrail> {
+        //tokens are never removed, so the keys are kept in mint order
+        let token_ids = self.token_metadata_by_id.keys_as_vector();
+
+        //fetch each token of the page by index, without going through the previous ones
+        paginate(token_ids.len(), from_index, limit)
+            .filter_map(|index| token_ids.get(index))
             //we'll map the token IDs which are strings into Json Tokens
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
+            .map(|token_id| self.trail_ticket(token_id).unwrap())
             .collect()
     }
 
@@ -78,29 +127,53 @@ impl NonFungibleTokenEnumeration for Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonTrail> {
-        //get the set of tokens for the passed in owner
-        let tokens_for_owner_set = self.tokens_per_owner.get(&account_id);
-        //if there is some set of tokens, we'll set the tokens variable equal to that set
-        let tokens = if let Some(tokens_for_owner_set) = tokens_for_owner_set {
-            tokens_for_owner_set
-        } else {
-            //if there is no set of tokens, we'll simply return an empty vector.
-            return vec![];
+        //get the tokens for the passed in owner, ordered by mint index
+        let tokens = match self.tokens_per_owner_by_mint.get(&account_id) {
+            Some(tokens) => tokens,
+            //if there are no tokens, we'll simply return an empty vector.
+            None => return vec![],
         };
 
         //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
+        //capped to the number of tokens, as the tree cursor keeps stepping past its end when skipping
+        let start = from_index
+            .map_or(0, |index| index.0)
+            .min(tokens.len() as u128) as usize;
 
-        //iterate through the keys vector
+        //iterate through the tokens in mint order
         tokens
             .iter()
             //skip to the index we specified in the start variable
-            .skip(start as usize)
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
+            .skip(start)
+            .take(limit.unwrap_or(50) as usize)
             //we'll map the token IDs which are strings into Json Tokens
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
+            .map(|(_, token_id)| self.trail_ticket(token_id).unwrap())
+            .collect()
+    }
+
+    fn trail_tickets_for_owner_after(
+        &self,
+        account_id: AccountId,
+        after: Option<TrailIdAndCopyNumber>,
+        limit: Option<u64>,
+    ) -> Vec<JsonTrail> {
+        let tokens = match self.tokens_per_owner_by_mint.get(&account_id) {
+            Some(tokens) => tokens,
+            None => return vec![],
+        };
+
+        let limit = limit.unwrap_or(50) as usize;
+        let page: Vec<(u64, TrailIdAndCopyNumber)> = match after {
+            //the cursor may have been transferred since, its mint index is still a valid bound
+            Some(after) => tokens
+                .iter_from(self.internal_mint_index(&after))
+                .take(limit)
+                .collect(),
+            None => tokens.iter().take(limit).collect(),
+        };
+
+        page.into_iter()
+            .map(|(_, token_id)| self.trail_ticket(token_id).unwrap())
             .collect()
     }
 
@@ -110,7 +183,70 @@ impl NonFungibleTokenEnumeration for Contract {
         account_id: AccountId,
         from_index: Option<U128>,
         limit: Option<u64>,
-    ) -> Vec<JsonTrail> {
+    ) -> Vec<JsonToken> {
         self.trail_tickets_for_owner(account_id, from_index, limit)
+            .into_iter()
+            .map(JsonToken::from)
+            .collect()
+    }
+
+    fn nft_supply_for_series(&self, series_id: TrailId) -> U128 {
+        U128(
+            self.tokens_per_series
+                .get(&series_id)
+                .map_or(0, |tokens| tokens.len() as u128),
+        )
+    }
+
+    fn nft_tokens_for_series(
+        &self,
+        series_id: TrailId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        self.trail_tickets_for_series(series_id, from_index, limit)
+            .into_iter()
+            .map(JsonToken::from)
+            .collect()
+    }
+
+    fn trail_tickets_for_series(
+        &self,
+        series_id: TrailId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonTrail> {
+        let tokens = match self.tokens_per_series.get(&series_id) {
+            Some(tokens) => tokens,
+            None => return vec![],
+        };
+
+        let token_ids = tokens.as_vector();
+        paginate(token_ids.len(), from_index, limit)
+            .filter_map(|index| token_ids.get(index))
+            .map(|token_id| self.trail_ticket(token_id).unwrap())
+            .collect()
+    }
+
+    fn nft_holders_for_series(
+        &self,
+        series_id: TrailId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<SeriesHolder> {
+        let holders = match self.holders_per_series.get(&series_id) {
+            Some(holders) => holders,
+            None => return vec![],
+        };
+
+        let (account_ids, copies) = (holders.keys_as_vector(), holders.values_as_vector());
+        paginate(account_ids.len(), from_index, limit)
+            .filter_map(|index| {
+                Some(SeriesHolder {
+                    account_id: account_ids.get(index)?,
+                    copies: copies.get(index)?,
+                })
+            })
+            .collect()
     }
 }
@@ -0,0 +1,345 @@
+use crate::config::ConfigKey;
+use crate::pause::PausableOperation;
+use crate::*;
+use near_sdk::Gas;
+use std::fmt;
+
+/// Every way a Campground call can fail.
+///
+/// Failures panic with `Campground: <message> (E<code>)`.
+/// Codes are stable, so clients can map them to their own messages
+/// without matching the text, which may change.
+/// Codes are grouped by area: 1xx access, 2xx trails, 3xx copies,
+/// 4xx payments and 5xx administration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CampgroundError {
+    /// 101
+    NotOwner,
+    /// 102
+    NotAdmin,
+    /// 103
+    NotFinanceManager,
+    /// 104
+    NotSubAdmin,
+    /// 105
+    NotFiatMinter,
+    /// 106
+    NotModerator,
+    /// 107
+    NotGuardian,
+    /// 108, along with the action that was attempted.
+    NotTrailCreator(&'static str),
+    /// 109
+    NotProposedOwner,
+    /// 110
+    NotTrailCopyOwner,
+
+    /// 201
+    TrailNotFound,
+    /// 202
+    DuplicateTrailId,
+    /// 203
+    TrailFrozen,
+    /// 204
+    TrailAlreadyFrozen,
+    /// 205
+    TrailArchived,
+    /// 206
+    TrailAlreadyArchived,
+    /// 207
+    TrailNotArchived,
+    /// 208
+    TrailNotMintable,
+    /// 209
+    TrailNotMintableByUser,
+    /// 210
+    TrailSoldOut,
+    /// 211
+    TrailHasCirculatingCopies,
+    /// 212
+    SupplyBelowCirculating,
+    /// 213
+    NoTickets,
+    /// 214
+    NoResources,
+    /// 215
+    SlugLength,
+    /// 216
+    SlugDelimiter,
+    /// 217
+    SlugCharacters,
+    /// 218
+    SlugNumeric,
+    /// 219
+    NoTrailsCreated,
+    /// 220
+    InvalidMediaHash,
+    /// 221
+    MediaHashWithoutMedia,
+    /// 222, along with the field and its maximum length.
+    FieldTooLong(&'static str, usize),
+    /// 223, along with the maximum number of resources.
+    TooManyResources(usize),
+    /// 224, along with the field.
+    UnsupportedUriScheme(&'static str),
+    /// 225
+    InvalidCampgroundId,
+
+    /// 301
+    TrailCopyNotFound,
+    /// 302
+    TrailCopyAlreadyExists,
+    /// 303
+    SelfTransfer,
+    /// 304
+    NoTrailCopiesOwned,
+    /// 305
+    MalformedTrailCopyId,
+
+    /// 401, along with the maximum price allowed.
+    PriceTooHigh(Balance),
+    /// 402
+    WrongDeposit,
+    /// 403, along with the deposit required.
+    StorageNotCovered(Balance),
+    /// 404
+    OneYoctoRequired,
+    /// 405, along with the minimum gas required.
+    NotEnoughGas(Gas),
+
+    /// 501
+    AdminChangeNotFound,
+    /// 502
+    AdminChangeTimelocked,
+    /// 503
+    AlreadyOwner,
+    /// 504
+    NoPendingOwner,
+    /// 505
+    FeeOutOfRange,
+    /// 506
+    MinimumFeeTooHigh,
+    /// 507
+    DelayTooLong,
+    /// 508
+    OperationPaused(PausableOperation),
+    /// 509, along with the maximum price allowed.
+    MaxPriceOutOfRange(Balance),
+    /// 510
+    EmptyFallbackMedia,
+    /// 511, along with the key and the maximum value allowed.
+    LimitOutOfRange(ConfigKey, u32),
+    /// 512
+    MigrationInProgress,
+}
+
+impl CampgroundError {
+    /// Stable code identifying this error.
+    pub fn code(&self) -> u16 {
+        use CampgroundError::*;
+        match self {
+            NotOwner => 101,
+            NotAdmin => 102,
+            NotFinanceManager => 103,
+            NotSubAdmin => 104,
+            NotFiatMinter => 105,
+            NotModerator => 106,
+            NotGuardian => 107,
+            NotTrailCreator(_) => 108,
+            NotProposedOwner => 109,
+            NotTrailCopyOwner => 110,
+
+            TrailNotFound => 201,
+            DuplicateTrailId => 202,
+            TrailFrozen => 203,
+            TrailAlreadyFrozen => 204,
+            TrailArchived => 205,
+            TrailAlreadyArchived => 206,
+            TrailNotArchived => 207,
+            TrailNotMintable => 208,
+            TrailNotMintableByUser => 209,
+            TrailSoldOut => 210,
+            TrailHasCirculatingCopies => 211,
+            SupplyBelowCirculating => 212,
+            NoTickets => 213,
+            NoResources => 214,
+            SlugLength => 215,
+            SlugDelimiter => 216,
+            SlugCharacters => 217,
+            SlugNumeric => 218,
+            NoTrailsCreated => 219,
+            InvalidMediaHash => 220,
+            MediaHashWithoutMedia => 221,
+            FieldTooLong(..) => 222,
+            TooManyResources(_) => 223,
+            UnsupportedUriScheme(_) => 224,
+            InvalidCampgroundId => 225,
+
+            TrailCopyNotFound => 301,
+            TrailCopyAlreadyExists => 302,
+            SelfTransfer => 303,
+            NoTrailCopiesOwned => 304,
+            MalformedTrailCopyId => 305,
+
+            PriceTooHigh(_) => 401,
+            WrongDeposit => 402,
+            StorageNotCovered(_) => 403,
+            OneYoctoRequired => 404,
+            NotEnoughGas(_) => 405,
+
+            AdminChangeNotFound => 501,
+            AdminChangeTimelocked => 502,
+            AlreadyOwner => 503,
+            NoPendingOwner => 504,
+            FeeOutOfRange => 505,
+            MinimumFeeTooHigh => 506,
+            DelayTooLong => 507,
+            OperationPaused(_) => 508,
+            MaxPriceOutOfRange(_) => 509,
+            EmptyFallbackMedia => 510,
+            LimitOutOfRange(..) => 511,
+            MigrationInProgress => 512,
+        }
+    }
+
+    /// Human readable description of this error, in English.
+    pub fn message(&self) -> String {
+        use CampgroundError::*;
+        match self {
+            NotOwner => "Only contract owner can execute".to_string(),
+            NotAdmin => "Only contract owner or Admin can execute".to_string(),
+            NotFinanceManager => {
+                "Only contract owner, Admin or FinanceManager can execute".to_string()
+            }
+            NotSubAdmin => "Only Sub-admin can execute".to_string(),
+            NotFiatMinter => "Only FiatMinter can execute".to_string(),
+            NotModerator => "Only Moderator can execute".to_string(),
+            NotGuardian => "Only contract owner, Admin or Guardian can execute".to_string(),
+            NotTrailCreator(action) => format!("Only Trail creator can {}", action),
+            NotProposedOwner => "Only the proposed owner can accept the ownership".to_string(),
+            NotTrailCopyOwner => "Only owner can transfer trail".to_string(),
+
+            TrailNotFound => "Trail does not exist".to_string(),
+            DuplicateTrailId => "Duplicate series id".to_string(),
+            TrailFrozen => "Trail metadata is frozen".to_string(),
+            TrailAlreadyFrozen => "Trail metadata is already frozen".to_string(),
+            TrailArchived => "Trail is archived".to_string(),
+            TrailAlreadyArchived => "Trail is already archived".to_string(),
+            TrailNotArchived => "Trail is not archived".to_string(),
+            TrailNotMintable => "Trail is not mintable".to_string(),
+            TrailNotMintableByUser => "Trail is not allowed to be minted by user".to_string(),
+            TrailSoldOut => "No more minting allowed".to_string(),
+            TrailHasCirculatingCopies => {
+                "Only trails without circulating copies can be deleted".to_string()
+            }
+            SupplyBelowCirculating => {
+                "Total supply cannot be lower than circulating supply".to_string()
+            }
+            NoTickets => "At least 1 ticket is required per trail series".to_string(),
+            NoResources => "At least 1 resource is needed per trail".to_string(),
+            SlugLength => format!(
+                "Slug must have between 1 and {} characters",
+                MAX_SLUG_LENGTH
+            ),
+            SlugDelimiter => format!("Slug cannot contain `{}`", TRAIL_DELIMETER),
+            SlugCharacters => {
+                "Slug can only contain ASCII letters, digits, `-` and `_`".to_string()
+            }
+            SlugNumeric => "Slug cannot be a number".to_string(),
+            NoTrailsCreated => "Account has not created any trails".to_string(),
+            InvalidMediaHash => "Media hash must be a base64-encoded SHA-256 hash".to_string(),
+            MediaHashWithoutMedia => "Media hash cannot be set without media".to_string(),
+            FieldTooLong(field, max_length) => {
+                format!("{} cannot be longer than {} bytes", field, max_length)
+            }
+            TooManyResources(max_resources) => {
+                format!("Trails cannot have more than {} resources", max_resources)
+            }
+            UnsupportedUriScheme(field) => format!(
+                "{} must be a relative path or use one of the schemes {}",
+                field,
+                ALLOWED_URI_SCHEMES.join(", ")
+            ),
+            InvalidCampgroundId => format!(
+                "Campground id must have between 1 and {} ASCII letters, digits, `-` or `_`",
+                MAX_CAMPGROUND_ID_LENGTH
+            ),
+
+            TrailCopyNotFound => "Trail copy does not exist".to_string(),
+            TrailCopyAlreadyExists => "Trail copy already exists".to_string(),
+            SelfTransfer => "The trail owner and receiver must be different".to_string(),
+            NoTrailCopiesOwned => "Account does not own any trail copies".to_string(),
+            MalformedTrailCopyId => format!(
+                "Trail copy id must be a trail id and a copy number separated by `{}`",
+                TRAIL_DELIMETER
+            ),
+
+            PriceTooHigh(max_price) => format!("price higher than {}", max_price),
+            WrongDeposit => {
+                "Attached deposit needs to be equal to ITO price or Campground Fee".to_string()
+            }
+            StorageNotCovered(required) => {
+                format!("Must attach {} yoctoNEAR to cover storage", required)
+            }
+            OneYoctoRequired => "Requires attached deposit of exactly 1 yoctoNEAR".to_string(),
+            NotEnoughGas(gas) => format!(
+                "You cannot attach less than {:?} Gas to nft_transfer_call",
+                gas
+            ),
+
+            AdminChangeNotFound => "Admin change does not exist".to_string(),
+            AdminChangeTimelocked => "Admin change is still timelocked".to_string(),
+            AlreadyOwner => "Account is already the contract owner".to_string(),
+            NoPendingOwner => "There is no pending ownership transfer".to_string(),
+            FeeOutOfRange => "Fee must be between 0 and 100".to_string(),
+            MinimumFeeTooHigh => "Minimum fee cannot exceed MAX_CAMPGROUND_MINIMUM_FEE".to_string(),
+            DelayTooLong => "Delay cannot exceed MAX_ADMIN_CHANGE_DELAY".to_string(),
+            OperationPaused(operation) => format!("Operation `{:?}` is paused", operation),
+            MaxPriceOutOfRange(max_price) => {
+                format!("MaxPrice must be between 1 and {}", max_price)
+            }
+            EmptyFallbackMedia => "FallbackMedia cannot be empty".to_string(),
+            LimitOutOfRange(key, max) => format!("{:?} must be between 1 and {}", key, max),
+            MigrationInProgress => "Migration is in progress, see `migrate_v4_batch`".to_string(),
+        }
+    }
+
+    /// Aborts the execution with this error.
+    pub fn panic(&self) -> ! {
+        panic!("{}", self)
+    }
+}
+
+impl fmt::Display for CampgroundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Campground: {} (E{})", self.message(), self.code())
+    }
+}
+
+/// Panics with `error` unless `condition` holds.
+pub(crate) fn require(condition: bool, error: CampgroundError) {
+    if !condition {
+        error.panic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_message_includes_code() {
+        assert_eq!(
+            CampgroundError::TrailNotFound.to_string(),
+            "Campground: Trail does not exist (E201)"
+        );
+        assert_eq!(
+            CampgroundError::NotTrailCreator("delete it").to_string(),
+            "Campground: Only Trail creator can delete it (E108)"
+        );
+        assert_eq!(
+            CampgroundError::OperationPaused(PausableOperation::Buy).to_string(),
+            "Campground: Operation `Buy` is paused (E508)"
+        );
+    }
+}
@@ -1,5 +1,10 @@
 use std::fmt::Display;
 
+use crate::admin::AdminChange;
+use crate::config::{ConfigEntry, ConfigKey};
+use crate::pause::PausableOperation;
+use crate::roles::Role;
+use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -9,6 +14,7 @@ use serde_with::skip_serializing_none;
 #[serde(crate = "near_sdk::serde")]
 pub enum NearEvent {
     Nep171(Nep171Event),
+    Campground(CampgroundEvent),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -29,6 +35,40 @@ pub enum Nep171EventKind {
     NftTransfer(Vec<NftTransferData>),
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampgroundEvent {
+    pub version: String,
+    #[serde(flatten)]
+    pub event_kind: CampgroundEventKind,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+pub enum CampgroundEventKind {
+    SeriesCreate(Vec<SeriesCreateData>),
+    SeriesPriceUpdate(Vec<SeriesPriceData>),
+    SeriesMintabilityUpdate(Vec<SeriesMintabilityData>),
+    SeriesPurchase(Vec<PurchaseData>),
+    SeriesSupplyUpdate(Vec<SeriesSupplyData>),
+    SeriesSaleClose(Vec<SeriesSupplyData>),
+    SeriesFreeze(Vec<SeriesFreezeData>),
+    SeriesMetadataUpdate(Vec<SeriesMetadataData>),
+    RoleGrant(Vec<RoleData>),
+    RoleRevoke(Vec<RoleData>),
+    OwnershipTransferPropose(Vec<OwnershipTransferData>),
+    OwnershipTransferAccept(Vec<OwnershipTransferData>),
+    OwnershipTransferCancel(Vec<OwnershipTransferData>),
+    ContractPause(Vec<PauseData>),
+    ContractUnpause(Vec<PauseData>),
+    AdminChangeQueue(Vec<AdminChangeData>),
+    AdminChangeExecute(Vec<AdminChangeData>),
+    AdminChangeCancel(Vec<AdminChangeData>),
+    ConfigUpdate(Vec<ConfigData>),
+}
+
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
@@ -49,6 +89,129 @@ pub struct NftTransferData {
     pub memo: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesCreateData {
+    pub series_id: String,
+    pub creator_id: String,
+    pub price: U128,
+    pub total: u64,
+    pub is_mintable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesPriceData {
+    pub series_id: String,
+    pub previous_price: U128,
+    pub price: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesMintabilityData {
+    pub series_id: String,
+    pub is_mintable: bool,
+    pub sender_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesFreezeData {
+    pub series_id: String,
+    pub creator_id: String,
+}
+
+/// `updated_at` is in milliseconds since the Unix epoch.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesMetadataData {
+    pub series_id: String,
+    pub creator_id: String,
+    pub updated_at: u64,
+}
+
+/// How a copy was paid for.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PaymentMethod {
+    /// Bought with NEAR through `nft_buy_series`.
+    Near,
+    /// Paid off-chain and minted by a `FiatMinter` through `nft_mint`.
+    FiatMinted,
+    /// Minted by the trail creator through `nft_mint`.
+    CreatorMinted,
+}
+
+/// Amounts are in yoctoNEAR.
+/// `amount` is the price paid on-chain, split into `campground_fee` and `creator_proceeds`,
+/// so it is 0 for copies paid off-chain or minted by the creator.
+/// `storage_deposit` is the deposit attached to cover storage instead,
+/// and `refund` the part of the attached deposit sent back to the buyer.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PurchaseData {
+    pub buyer_id: String,
+    pub receiver_id: String,
+    pub series_id: String,
+    pub token_id: String,
+    pub amount: U128,
+    pub campground_fee: U128,
+    pub creator_proceeds: U128,
+    pub payment_method: PaymentMethod,
+    pub storage_deposit: U128,
+    pub refund: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesSupplyData {
+    pub series_id: String,
+    pub previous_total: u64,
+    pub total: u64,
+    pub circulating: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleData {
+    pub role: Role,
+    pub account_id: String,
+    pub sender_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipTransferData {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseData {
+    pub operation: PausableOperation,
+    pub sender_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminChangeData {
+    pub change_id: u64,
+    pub change: AdminChange,
+    pub executable_at: u64,
+}
+
+/// `value` is `None` when the key is restored to its default.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ConfigData {
+    pub key: ConfigKey,
+    pub value: Option<ConfigEntry>,
+    pub sender_id: String,
+}
+
 impl Display for NearEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!("EVENT_JSON:{}", self.to_json_string()))
@@ -67,6 +230,17 @@ impl NearEvent {
         NearEvent::new_171("1.0.0".to_string(), event_kind)
     }
 
+    pub fn new_campground(version: String, event_kind: CampgroundEventKind) -> Self {
+        NearEvent::Campground(CampgroundEvent {
+            version,
+            event_kind,
+        })
+    }
+
+    pub fn new_campground_v1(event_kind: CampgroundEventKind) -> Self {
+        NearEvent::new_campground("1.0.0".to_string(), event_kind)
+    }
+
     pub fn nft_transfer(data: Vec<NftTransferData>) -> Self {
         NearEvent::new_171_v1(Nep171EventKind::NftTransfer(data))
     }
@@ -75,6 +249,82 @@ impl NearEvent {
         NearEvent::new_171_v1(Nep171EventKind::NftMint(data))
     }
 
+    pub fn series_create(data: Vec<SeriesCreateData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesCreate(data))
+    }
+
+    pub fn series_price_update(data: Vec<SeriesPriceData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesPriceUpdate(data))
+    }
+
+    pub fn series_mintability_update(data: Vec<SeriesMintabilityData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesMintabilityUpdate(data))
+    }
+
+    pub fn series_purchase(data: Vec<PurchaseData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesPurchase(data))
+    }
+
+    pub fn series_supply_update(data: Vec<SeriesSupplyData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesSupplyUpdate(data))
+    }
+
+    pub fn series_sale_close(data: Vec<SeriesSupplyData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesSaleClose(data))
+    }
+
+    pub fn series_freeze(data: Vec<SeriesFreezeData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesFreeze(data))
+    }
+
+    pub fn series_metadata_update(data: Vec<SeriesMetadataData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::SeriesMetadataUpdate(data))
+    }
+
+    pub fn role_grant(data: Vec<RoleData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::RoleGrant(data))
+    }
+
+    pub fn role_revoke(data: Vec<RoleData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::RoleRevoke(data))
+    }
+
+    pub fn ownership_transfer_propose(data: Vec<OwnershipTransferData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::OwnershipTransferPropose(data))
+    }
+
+    pub fn ownership_transfer_accept(data: Vec<OwnershipTransferData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::OwnershipTransferAccept(data))
+    }
+
+    pub fn ownership_transfer_cancel(data: Vec<OwnershipTransferData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::OwnershipTransferCancel(data))
+    }
+
+    pub fn contract_pause(data: Vec<PauseData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::ContractPause(data))
+    }
+
+    pub fn contract_unpause(data: Vec<PauseData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::ContractUnpause(data))
+    }
+
+    pub fn admin_change_queue(data: Vec<AdminChangeData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::AdminChangeQueue(data))
+    }
+
+    pub fn admin_change_execute(data: Vec<AdminChangeData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::AdminChangeExecute(data))
+    }
+
+    pub fn admin_change_cancel(data: Vec<AdminChangeData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::AdminChangeCancel(data))
+    }
+
+    pub fn config_update(data: Vec<ConfigData>) -> Self {
+        NearEvent::new_campground_v1(CampgroundEventKind::ConfigUpdate(data))
+    }
+
     pub(crate) fn to_json_string(&self) -> String {
         near_sdk::serde_json::to_string(self).unwrap()
     }
@@ -114,6 +364,186 @@ impl NearEvent {
     pub fn log_nft_transfers(data: Vec<NftTransferData>) {
         NearEvent::nft_transfer(data).log();
     }
+
+    pub fn log_series_create(
+        series_id: String,
+        creator_id: String,
+        price: U128,
+        total: u64,
+        is_mintable: bool,
+    ) {
+        NearEvent::series_create(vec![SeriesCreateData {
+            series_id,
+            creator_id,
+            price,
+            total,
+            is_mintable,
+        }])
+        .log();
+    }
+
+    pub fn log_series_price_update(series_id: String, previous_price: U128, price: U128) {
+        NearEvent::series_price_update(vec![SeriesPriceData {
+            series_id,
+            previous_price,
+            price,
+        }])
+        .log();
+    }
+
+    pub fn log_series_mintability_update(series_id: String, is_mintable: bool, sender_id: String) {
+        NearEvent::series_mintability_update(vec![SeriesMintabilityData {
+            series_id,
+            is_mintable,
+            sender_id,
+        }])
+        .log();
+    }
+
+    pub fn log_series_purchase(data: PurchaseData) {
+        NearEvent::series_purchase(vec![data]).log();
+    }
+
+    pub fn log_series_supply_update(
+        series_id: String,
+        previous_total: u64,
+        total: u64,
+        circulating: u64,
+    ) {
+        NearEvent::series_supply_update(vec![SeriesSupplyData {
+            series_id,
+            previous_total,
+            total,
+            circulating,
+        }])
+        .log();
+    }
+
+    pub fn log_series_sale_close(
+        series_id: String,
+        previous_total: u64,
+        total: u64,
+        circulating: u64,
+    ) {
+        NearEvent::series_sale_close(vec![SeriesSupplyData {
+            series_id,
+            previous_total,
+            total,
+            circulating,
+        }])
+        .log();
+    }
+
+    pub fn log_series_freeze(series_id: String, creator_id: String) {
+        NearEvent::series_freeze(vec![SeriesFreezeData {
+            series_id,
+            creator_id,
+        }])
+        .log();
+    }
+
+    pub fn log_series_metadata_update(series_id: String, creator_id: String, updated_at: u64) {
+        NearEvent::series_metadata_update(vec![SeriesMetadataData {
+            series_id,
+            creator_id,
+            updated_at,
+        }])
+        .log();
+    }
+
+    pub fn log_role_grant(role: Role, account_id: String, sender_id: String) {
+        NearEvent::role_grant(vec![RoleData {
+            role,
+            account_id,
+            sender_id,
+        }])
+        .log();
+    }
+
+    pub fn log_role_revoke(role: Role, account_id: String, sender_id: String) {
+        NearEvent::role_revoke(vec![RoleData {
+            role,
+            account_id,
+            sender_id,
+        }])
+        .log();
+    }
+
+    pub fn log_ownership_transfer_propose(old_owner_id: String, new_owner_id: String) {
+        NearEvent::ownership_transfer_propose(vec![OwnershipTransferData {
+            old_owner_id,
+            new_owner_id,
+        }])
+        .log();
+    }
+
+    pub fn log_ownership_transfer_accept(old_owner_id: String, new_owner_id: String) {
+        NearEvent::ownership_transfer_accept(vec![OwnershipTransferData {
+            old_owner_id,
+            new_owner_id,
+        }])
+        .log();
+    }
+
+    pub fn log_ownership_transfer_cancel(old_owner_id: String, new_owner_id: String) {
+        NearEvent::ownership_transfer_cancel(vec![OwnershipTransferData {
+            old_owner_id,
+            new_owner_id,
+        }])
+        .log();
+    }
+
+    pub fn log_contract_pause(operation: PausableOperation, sender_id: String) {
+        NearEvent::contract_pause(vec![PauseData {
+            operation,
+            sender_id,
+        }])
+        .log();
+    }
+
+    pub fn log_contract_unpause(operation: PausableOperation, sender_id: String) {
+        NearEvent::contract_unpause(vec![PauseData {
+            operation,
+            sender_id,
+        }])
+        .log();
+    }
+
+    pub fn log_admin_change_queue(change_id: u64, change: AdminChange, executable_at: u64) {
+        NearEvent::admin_change_queue(vec![AdminChangeData {
+            change_id,
+            change,
+            executable_at,
+        }])
+        .log();
+    }
+
+    pub fn log_admin_change_execute(change_id: u64, change: AdminChange, executable_at: u64) {
+        NearEvent::admin_change_execute(vec![AdminChangeData {
+            change_id,
+            change,
+            executable_at,
+        }])
+        .log();
+    }
+
+    pub fn log_admin_change_cancel(change_id: u64, change: AdminChange, executable_at: u64) {
+        NearEvent::admin_change_cancel(vec![AdminChangeData {
+            change_id,
+            change,
+            executable_at,
+        }])
+        .log();
+    }
+
+    pub fn log_config_update(key: ConfigKey, value: Option<ConfigEntry>, sender_id: String) {
+        NearEvent::config_update(vec![ConfigData {
+            key,
+            value,
+            sender_id,
+        }])
+        .log();
+    }
 }
 
 #[cfg(test)]
@@ -209,4 +639,282 @@ mod tests {
             r#"{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"authorized_id":"4","old_owner_id":"alice","new_owner_id":"bob","token_ids":["2","3"],"memo":"has memo"},{"old_owner_id":"bob","new_owner_id":"alice","token_ids":["0","1"]}]}"#
         );
     }
+
+    #[test]
+    fn series_supply_update() {
+        let log = NearEvent::series_supply_update(vec![SeriesSupplyData {
+            series_id: "1".to_string(),
+            previous_total: 10,
+            total: 20,
+            circulating: 10,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_supply_update","data":[{"series_id":"1","previous_total":10,"total":20,"circulating":10}]}"#
+        );
+    }
+
+    #[test]
+    fn series_sale_close() {
+        let log = NearEvent::series_sale_close(vec![SeriesSupplyData {
+            series_id: "1".to_string(),
+            previous_total: 10,
+            total: 4,
+            circulating: 4,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_sale_close","data":[{"series_id":"1","previous_total":10,"total":4,"circulating":4}]}"#
+        );
+    }
+
+    #[test]
+    fn role_grant() {
+        let log = NearEvent::role_grant(vec![RoleData {
+            role: Role::FiatMinter,
+            account_id: "carol".to_string(),
+            sender_id: "alice".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"role_grant","data":[{"role":"FiatMinter","account_id":"carol","sender_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn role_revoke() {
+        let log = NearEvent::role_revoke(vec![RoleData {
+            role: Role::SubAdmin,
+            account_id: "bob".to_string(),
+            sender_id: "alice".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"role_revoke","data":[{"role":"SubAdmin","account_id":"bob","sender_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn ownership_transfer_propose() {
+        let log = NearEvent::ownership_transfer_propose(vec![OwnershipTransferData {
+            old_owner_id: "alice".to_string(),
+            new_owner_id: "bob".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"ownership_transfer_propose","data":[{"old_owner_id":"alice","new_owner_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn ownership_transfer_accept() {
+        let log = NearEvent::ownership_transfer_accept(vec![OwnershipTransferData {
+            old_owner_id: "alice".to_string(),
+            new_owner_id: "bob".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"ownership_transfer_accept","data":[{"old_owner_id":"alice","new_owner_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn ownership_transfer_cancel() {
+        let log = NearEvent::ownership_transfer_cancel(vec![OwnershipTransferData {
+            old_owner_id: "alice".to_string(),
+            new_owner_id: "bob".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"ownership_transfer_cancel","data":[{"old_owner_id":"alice","new_owner_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn contract_pause() {
+        let log = NearEvent::contract_pause(vec![PauseData {
+            operation: PausableOperation::Buy,
+            sender_id: "alice".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"contract_pause","data":[{"operation":"Buy","sender_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn contract_unpause() {
+        let log = NearEvent::contract_unpause(vec![PauseData {
+            operation: PausableOperation::Transfer,
+            sender_id: "alice".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"contract_unpause","data":[{"operation":"Transfer","sender_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn admin_change_queue() {
+        let log = NearEvent::admin_change_queue(vec![AdminChangeData {
+            change_id: 1,
+            change: AdminChange::Fee(10),
+            executable_at: 172800000000000,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"admin_change_queue","data":[{"change_id":1,"change":{"Fee":10},"executable_at":172800000000000}]}"#
+        );
+    }
+
+    #[test]
+    fn admin_change_execute() {
+        let log = NearEvent::admin_change_execute(vec![AdminChangeData {
+            change_id: 2,
+            change: AdminChange::MinimumFee(100.into()),
+            executable_at: 172800000000000,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"admin_change_execute","data":[{"change_id":2,"change":{"MinimumFee":"100"},"executable_at":172800000000000}]}"#
+        );
+    }
+
+    #[test]
+    fn admin_change_cancel() {
+        let log = NearEvent::admin_change_cancel(vec![AdminChangeData {
+            change_id: 3,
+            change: AdminChange::TreasuryAddress("treasury".parse().unwrap()),
+            executable_at: 172800000000000,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"admin_change_cancel","data":[{"change_id":3,"change":{"TreasuryAddress":"treasury"},"executable_at":172800000000000}]}"#
+        );
+    }
+
+    #[test]
+    fn series_create() {
+        let log = NearEvent::series_create(vec![SeriesCreateData {
+            series_id: "1".to_string(),
+            creator_id: "alice".to_string(),
+            price: U128(1000),
+            total: 10,
+            is_mintable: true,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_create","data":[{"series_id":"1","creator_id":"alice","price":"1000","total":10,"is_mintable":true}]}"#
+        );
+    }
+
+    #[test]
+    fn series_price_update() {
+        let log = NearEvent::series_price_update(vec![SeriesPriceData {
+            series_id: "1".to_string(),
+            previous_price: U128(1000),
+            price: U128(2000),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_price_update","data":[{"series_id":"1","previous_price":"1000","price":"2000"}]}"#
+        );
+    }
+
+    #[test]
+    fn series_mintability_update() {
+        let log = NearEvent::series_mintability_update(vec![SeriesMintabilityData {
+            series_id: "1".to_string(),
+            is_mintable: false,
+            sender_id: "bob".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_mintability_update","data":[{"series_id":"1","is_mintable":false,"sender_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn series_freeze() {
+        let log = NearEvent::series_freeze(vec![SeriesFreezeData {
+            series_id: "1".to_string(),
+            creator_id: "alice".to_string(),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_freeze","data":[{"series_id":"1","creator_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn series_metadata_update() {
+        let log = NearEvent::series_metadata_update(vec![SeriesMetadataData {
+            series_id: "1".to_string(),
+            creator_id: "alice".to_string(),
+            updated_at: 1000,
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_metadata_update","data":[{"series_id":"1","creator_id":"alice","updated_at":1000}]}"#
+        );
+    }
+
+    #[test]
+    fn config_update() {
+        let log = NearEvent::config_update(vec![
+            ConfigData {
+                key: ConfigKey::MaxPrice,
+                value: Some(ConfigEntry::MaxPrice(U128(1000))),
+                sender_id: "alice".to_string(),
+            },
+            ConfigData {
+                key: ConfigKey::MaxPrice,
+                value: None,
+                sender_id: "alice".to_string(),
+            },
+        ])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"config_update","data":[{"key":"MaxPrice","value":{"MaxPrice":"1000"},"sender_id":"alice"},{"key":"MaxPrice","sender_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn series_purchase() {
+        let log = NearEvent::series_purchase(vec![PurchaseData {
+            buyer_id: "bob".to_string(),
+            receiver_id: "carol".to_string(),
+            series_id: "1".to_string(),
+            token_id: "1:1".to_string(),
+            amount: U128(1000),
+            campground_fee: U128(50),
+            creator_proceeds: U128(950),
+            payment_method: PaymentMethod::Near,
+            storage_deposit: U128(0),
+            refund: U128(0),
+        }])
+        .to_json_string();
+        assert_eq!(
+            log,
+            r#"{"standard":"campground","version":"1.0.0","event":"series_purchase","data":[{"buyer_id":"bob","receiver_id":"carol","series_id":"1","token_id":"1:1","amount":"1000","campground_fee":"50","creator_proceeds":"950","payment_method":"Near","storage_deposit":"0","refund":"0"}]}"#
+        );
+    }
 }
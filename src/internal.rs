@@ -1,8 +1,11 @@
 use crate::bridge::SeriesBridge;
+use crate::pause::PausableOperation;
+use crate::roles::Role;
+use crate::sub_admin::SubAdminBridge;
 use crate::*;
 use near_sdk::CryptoHash;
 use std::mem::size_of;
-use crate::sub_admin::SubAdminBridge;
+use std::ops::Range;
 
 //used to generate a unique prefix in our storage collections (this is to avoid data collisions)
 pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
@@ -13,25 +16,42 @@ pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     hash
 }
 
-//refund the initial deposit based on the amount of storage that was used up
-pub(crate) fn refund_deposit(storage_used: u64, extra_spend: Balance) {
+//used to generate a unique prefix for the collections of a trail series
+pub(crate) fn hash_trail_id(trail_id: &TrailId) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(trail_id.as_bytes()));
+    hash
+}
+
+// Makes sure the call was signed with a full access key
+pub(crate) fn assert_one_yocto() {
+    require(
+        env::attached_deposit() == 1,
+        CampgroundError::OneYoctoRequired,
+    );
+}
+
+//refund the initial deposit based on the amount of storage that was used up, returning the refunded amount
+pub(crate) fn refund_deposit(storage_used: u64, extra_spend: Balance) -> Balance {
     let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
     let attached_deposit = env::attached_deposit() - extra_spend;
 
-    assert!(
+    require(
         required_cost <= attached_deposit,
-        "Must attach {} yoctoNEAR to cover storage",
-        required_cost,
+        CampgroundError::StorageNotCovered(required_cost),
     );
 
     let refund = attached_deposit - required_cost;
     if refund > 1 {
         Promise::new(env::predecessor_account_id()).transfer(refund);
+        refund
+    } else {
+        0
     }
 }
 
 pub(crate) fn calculate_fee(price: u128, campground_fee: u64, min_treasury: u128) -> u128 {
-    let fee = (price as u128 * campground_fee as u128) / 100;
+    let fee = (price * campground_fee as u128) / 100;
     if fee < min_treasury {
         min_treasury
     } else {
@@ -66,7 +86,6 @@ where
 }
 
 //refund a map of approved account IDs and send the funds to the passed in account ID
-/// TODO: Check whether we will need this function. If not, remove it.
 pub fn refund_approved_account_ids(
     account_id: AccountId,
     approved_account_ids: &HashMap<AccountId, u64>,
@@ -75,18 +94,24 @@ pub fn refund_approved_account_ids(
     refund_approved_account_ids_iter(account_id, approved_account_ids.keys())
 }
 
+// Range of indexes in a page of `limit` items (50 by default) starting at `from_index`
+pub(crate) fn paginate(len: u64, from_index: Option<U128>, limit: Option<u64>) -> Range<u64> {
+    let start = u64::try_from(from_index.map_or(0, |index| index.0))
+        .unwrap_or(u64::MAX)
+        .min(len);
+    start..start.saturating_add(limit.unwrap_or(50)).min(len)
+}
+
 // Gets the id and copy of a trail based on TrailIdAndCopyNumber & TRAIL_DELIMETER
 pub(crate) fn get_id_and_copy(trail_id: TrailIdAndCopyNumber) -> (String, String) {
     let id_and_copy: Vec<&str> = trail_id.split(TRAIL_DELIMETER).collect();
     let id = id_and_copy
-        .get(0)
-        .expect("Id is not present")
-        .clone()
+        .first()
+        .unwrap_or_else(|| CampgroundError::MalformedTrailCopyId.panic())
         .to_string();
     let copy_number = id_and_copy
         .get(1)
-        .expect("Copy number is not present")
-        .clone()
+        .unwrap_or_else(|| CampgroundError::MalformedTrailCopyId.panic())
         .to_string();
     (id, copy_number)
 }
@@ -118,24 +143,90 @@ pub(crate) fn format_json_trail(
     }
 }
 
-pub(crate) fn partial_metadata_from_trail_series(trail_series: &TrailSeries) -> TokenMetadata {
-    TokenMetadata {
-        title: Some(trail_series.metadata.title.to_owned()),
-        description: Some(trail_series.metadata.description.to_owned()),
-        media: trail_series.metadata.media.to_owned().map(|v| v.to_owned()).or(Some(String::from("https://assets.website-files.com/6183638541c07be9ecbe4559/6221296e8a95113bac90ffdd_transparent%20background.png"))),
-        media_hash: None,
-        copies: None,
-        issued_at: None,
-        expires_at: None,
-        starts_at: None,
-        updated_at: None,
-        extra: None,
-        reference: None,
-        reference_hash: None
+// Resolves a relative `uri` against `base_uri`, absolute URIs are kept as they are
+pub(crate) fn resolve_uri(base_uri: Option<&str>, uri: String) -> String {
+    match base_uri {
+        Some(base_uri) if !uri.contains("://") && !uri.starts_with("data:") => format!(
+            "{}/{}",
+            base_uri.trim_end_matches('/'),
+            uri.trim_start_matches('/')
+        ),
+        _ => uri,
+    }
+}
+
+/// Panics if `value` is longer than `max_length` bytes.
+pub(crate) fn assert_max_length(field: &'static str, value: &str, max_length: usize) {
+    require(
+        value.len() <= max_length,
+        CampgroundError::FieldTooLong(field, max_length),
+    );
+}
+
+/// Panics if `uri` is too long or uses an unsupported scheme.
+pub(crate) fn assert_valid_uri(field: &'static str, uri: &str, max_length: usize) {
+    assert_max_length(field, uri, max_length);
+    assert_valid_uri_scheme(field, uri);
+}
+
+/// Panics if `uri` has a scheme outside of `ALLOWED_URI_SCHEMES`.
+/// URIs without a scheme are relative paths, resolved against `base_uri`.
+pub(crate) fn assert_valid_uri_scheme(field: &'static str, uri: &str) {
+    let scheme = uri
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains('/'));
+    if let Some(scheme) = scheme {
+        require(
+            ALLOWED_URI_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()),
+            CampgroundError::UnsupportedUriScheme(field),
+        );
     }
 }
 
 impl Contract {
+    // Metadata of a copy of `trail_series`, or of the series itself when `issued_at` is `None`
+    pub(crate) fn internal_token_metadata(
+        &self,
+        trail_series: &TrailSeries,
+        issued_at: Option<u64>,
+        updated_at: Option<u64>,
+    ) -> TokenMetadata {
+        let metadata = &trail_series.metadata;
+        let base_uri = self.metadata.get().and_then(|metadata| metadata.base_uri);
+        let base_uri = base_uri.as_deref();
+
+        // The hash only covers the media of the series, not the fallbacks
+        let (media, media_hash) = match &metadata.media {
+            Some(media) => (Some(media.to_owned()), metadata.media_hash.to_owned()),
+            None => (
+                metadata
+                    .fallback_media
+                    .to_owned()
+                    .or_else(|| self.config.fallback_media.clone()),
+                None,
+            ),
+        };
+
+        TokenMetadata {
+            title: Some(metadata.title.to_owned()),
+            description: Some(metadata.description.to_owned()),
+            media: media.map(|media| resolve_uri(base_uri, media)),
+            media_hash,
+            copies: Some(trail_series.supply.total),
+            issued_at,
+            expires_at: metadata.expires_at,
+            starts_at: metadata.starts_at,
+            updated_at,
+            extra: metadata.data.to_owned(),
+            reference: metadata
+                .reference
+                .to_owned()
+                .map(|reference| resolve_uri(base_uri, reference)),
+            reference_hash: None,
+        }
+    }
+
     //add a token to the set of tokens an owner has
     pub(crate) fn internal_add_trail_to_owner(
         &mut self,
@@ -148,7 +239,7 @@ impl Contract {
             UnorderedSet::new(
                 StorageKey::TokenPerOwnerInner {
                     //we get a new unique prefix for the collection
-                    account_id_hash: hash_account_id(&account_id),
+                    account_id_hash: hash_account_id(account_id),
                 }
                 .try_to_vec()
                 .unwrap(),
@@ -160,6 +251,152 @@ impl Contract {
 
         //we insert that set for the given account ID.
         self.tokens_per_owner.insert(account_id, &tokens_set);
+
+        self.internal_add_trail_to_owner_by_mint(account_id, token_id);
+        self.internal_add_holder_copy(&get_id_and_copy(token_id.clone()).0, account_id);
+    }
+
+    //add a token to the tokens an owner has, ordered by mint index
+    pub(crate) fn internal_add_trail_to_owner_by_mint(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TrailIdAndCopyNumber,
+    ) {
+        let mut tokens = self
+            .tokens_per_owner_by_mint
+            .get(account_id)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::TokensPerOwnerByMintInner {
+                        account_id_hash: hash_account_id(account_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+
+        tokens.insert(&self.internal_mint_index(token_id), token_id);
+
+        self.tokens_per_owner_by_mint.insert(account_id, &tokens);
+    }
+
+    //remove a token from the tokens an owner has, ordered by mint index
+    fn internal_remove_trail_from_owner_by_mint(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TrailIdAndCopyNumber,
+    ) {
+        let mut tokens = self
+            .tokens_per_owner_by_mint
+            .get(account_id)
+            .unwrap_or_else(|| CampgroundError::NoTrailCopiesOwned.panic());
+
+        tokens.remove(&self.internal_mint_index(token_id));
+
+        if tokens.is_empty() {
+            self.tokens_per_owner_by_mint.remove(account_id);
+        } else {
+            self.tokens_per_owner_by_mint.insert(account_id, &tokens);
+        }
+    }
+
+    //position of a token in mint order
+    pub(crate) fn internal_mint_index(&self, token_id: &TrailIdAndCopyNumber) -> u64 {
+        self.token_mint_index
+            .get(token_id)
+            .unwrap_or_else(|| CampgroundError::TrailCopyNotFound.panic())
+    }
+
+    //add a freshly minted token to the set of tokens of its trail series
+    pub(crate) fn internal_add_trail_to_series(
+        &mut self,
+        series_id: &TrailId,
+        token_id: &TrailIdAndCopyNumber,
+    ) {
+        let mut tokens_set = self.tokens_per_series.get(series_id).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::TokensPerTypeInner {
+                    token_type_hash: hash_trail_id(series_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        tokens_set.insert(token_id);
+
+        self.tokens_per_series.insert(series_id, &tokens_set);
+    }
+
+    //count one more copy of a trail series held by an account, in both `holders_per_series` and `series_per_owner`
+    pub(crate) fn internal_add_holder_copy(&mut self, series_id: &TrailId, account_id: &AccountId) {
+        let mut holders = self.holders_per_series.get(series_id).unwrap_or_else(|| {
+            UnorderedMap::new(
+                StorageKey::HoldersPerSeriesInner {
+                    trail_id_hash: hash_trail_id(series_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        let copies = holders.get(account_id).unwrap_or(0);
+        holders.insert(account_id, &(copies + 1));
+
+        self.holders_per_series.insert(series_id, &holders);
+
+        let mut series = self.series_per_owner.get(account_id).unwrap_or_else(|| {
+            UnorderedMap::new(
+                StorageKey::SeriesPerOwnerInner {
+                    account_id_hash: hash_account_id(account_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        series.insert(series_id, &(copies + 1));
+        self.series_per_owner.insert(account_id, &series);
+    }
+
+    //count one less copy of a trail series held by an account, dropping the holder at zero
+    pub(crate) fn internal_remove_holder_copy(
+        &mut self,
+        series_id: &TrailId,
+        account_id: &AccountId,
+    ) {
+        let mut holders = self
+            .holders_per_series
+            .get(series_id)
+            .unwrap_or_else(|| CampgroundError::NoTrailCopiesOwned.panic());
+
+        let mut series = self
+            .series_per_owner
+            .get(account_id)
+            .unwrap_or_else(|| CampgroundError::NoTrailCopiesOwned.panic());
+
+        match holders.get(account_id) {
+            Some(copies) if copies > 1 => {
+                holders.insert(account_id, &(copies - 1));
+                series.insert(series_id, &(copies - 1));
+            }
+            Some(_) => {
+                holders.remove(account_id);
+                series.remove(series_id);
+            }
+            None => CampgroundError::NoTrailCopiesOwned.panic(),
+        }
+
+        if holders.is_empty() {
+            self.holders_per_series.remove(series_id);
+        } else {
+            self.holders_per_series.insert(series_id, &holders);
+        }
+
+        if series.is_empty() {
+            self.series_per_owner.remove(account_id);
+        } else {
+            self.series_per_owner.insert(account_id, &series);
+        }
     }
 
     pub(crate) fn internal_add_trail_to_creator(
@@ -173,7 +410,14 @@ impl Contract {
             .get(account_id)
             .unwrap_or_else(|| {
                 //if the account doesn't have any tokens, we create a new unordered set
-                UnorderedSet::new(StorageKey::TokenPerCreator.try_to_vec().unwrap())
+                UnorderedSet::new(
+                    StorageKey::TokenPerCreatorInner {
+                        //we get a new unique prefix for the collection
+                        account_id_hash: hash_account_id(account_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
             });
 
         //we insert the token ID into the set
@@ -184,6 +428,27 @@ impl Contract {
             .insert(account_id, &trails_set);
     }
 
+    //remove a trail from the set of trails a creator has
+    pub(crate) fn internal_remove_trail_from_creator(
+        &mut self,
+        account_id: &AccountId,
+        trail_id: &TrailId,
+    ) {
+        let mut trails_set = self
+            .trails_series_by_creator
+            .get(account_id)
+            .unwrap_or_else(|| CampgroundError::NoTrailsCreated.panic());
+
+        trails_set.remove(trail_id);
+
+        if trails_set.is_empty() {
+            self.trails_series_by_creator.remove(account_id);
+        } else {
+            self.trails_series_by_creator
+                .insert(account_id, &trails_set);
+        }
+    }
+
     //remove a token from an owner (internal method and can't be called directly via CLI).
     pub(crate) fn internal_remove_trail_from_owner(
         &mut self,
@@ -195,11 +460,14 @@ impl Contract {
             .tokens_per_owner
             .get(account_id)
             //if there is no set of tokens for the owner, we panic with the following message:
-            .expect("Account does not own any tokens");
+            .unwrap_or_else(|| CampgroundError::NoTrailCopiesOwned.panic());
 
         //we remove the the token_id from the set of tokens
         tokens_set.remove(trail_id);
 
+        self.internal_remove_trail_from_owner_by_mint(account_id, trail_id);
+        self.internal_remove_holder_copy(&get_id_and_copy(trail_id.clone()).0, account_id);
+
         //if the token set is now empty, we remove the owner from the tokens_per_owner collection
         if tokens_set.is_empty() {
             self.tokens_per_owner.remove(account_id);
@@ -209,24 +477,26 @@ impl Contract {
         }
     }
 
+    // Moves a trail copy from its owner to `receiver_id`, returning the copy as it was before.
+    // Trails do not support approvals, so only the owner can transfer them.
     pub(crate) fn internal_transfer(
         &mut self,
         sender_id: &AccountId,
         receiver_id: &AccountId,
         trail_id: &TrailIdAndCopyNumber,
-        //we introduce an approval ID so that people with that approval ID can transfer the token
-        _approval_id: Option<u64>,
-        _memo: Option<String>,
-    ) -> (TrailBusiness, TrailBusiness) {
+    ) -> TrailBusiness {
         let trail = self
             .tokens_by_id
             .get(trail_id)
-            .expect("Trail does not exist");
+            .unwrap_or_else(|| CampgroundError::TrailCopyNotFound.panic());
 
-        assert_eq!(sender_id, &trail.owner_id, "Only owner can transfer trail");
-        assert_ne!(
-            receiver_id, &trail.owner_id,
-            "The trail owner and receiver must be different"
+        require(
+            sender_id == &trail.owner_id,
+            CampgroundError::NotTrailCopyOwner,
+        );
+        require(
+            receiver_id != &trail.owner_id,
+            CampgroundError::SelfTransfer,
         );
 
         self.internal_remove_trail_from_owner(&trail.owner_id, trail_id);
@@ -237,24 +507,132 @@ impl Contract {
 
         self.tokens_by_id.insert(trail_id, &new_trail_business);
 
-        (new_trail_business, trail)
+        trail
     }
 
     pub(crate) fn panic_if_not_owner(&self) {
         if !self.is_caller_contract_owner() {
-            panic!("Campground: Only contract owner can execute")
+            CampgroundError::NotOwner.panic()
+        }
+    }
+
+    pub(crate) fn panic_if_not_admin(&self) {
+        if !self.is_caller_contract_owner() && !self.is_caller_in_role(Role::Admin) {
+            CampgroundError::NotAdmin.panic()
+        }
+    }
+
+    pub(crate) fn panic_if_not_finance_manager(&self) {
+        if !self.is_caller_contract_owner()
+            && !self.is_caller_in_role(Role::Admin)
+            && !self.is_caller_in_role(Role::FinanceManager)
+        {
+            CampgroundError::NotFinanceManager.panic()
         }
     }
 
     pub(crate) fn panic_if_not_subadmin(&self) {
         if !self.is_caller_subadmin() {
-            panic!("Campground: Only Sub-admin can execute")
+            CampgroundError::NotSubAdmin.panic()
+        }
+    }
+
+    pub(crate) fn panic_if_not_fiat_minter(&self) {
+        if !self.is_caller_contract_owner()
+            && !self.is_caller_in_role(Role::Admin)
+            && !self.is_caller_in_role(Role::FiatMinter)
+        {
+            CampgroundError::NotFiatMinter.panic()
+        }
+    }
+
+    pub(crate) fn panic_if_not_moderator(&self) {
+        if !self.is_caller_contract_owner()
+            && !self.is_caller_in_role(Role::Admin)
+            && !self.is_caller_in_role(Role::Moderator)
+        {
+            CampgroundError::NotModerator.panic()
+        }
+    }
+
+    pub(crate) fn panic_if_not_guardian(&self) {
+        if !self.is_caller_contract_owner()
+            && !self.is_caller_in_role(Role::Admin)
+            && !self.is_caller_in_role(Role::Guardian)
+        {
+            CampgroundError::NotGuardian.panic()
+        }
+    }
+
+    // Every pausable operation reads trails or tokens, which are not all migrated yet
+    pub(crate) fn panic_if_paused(&self, operation: PausableOperation) {
+        self.panic_if_migrating();
+        if self.paused_operations.contains(&operation) {
+            CampgroundError::OperationPaused(operation).panic()
         }
     }
 
     pub(crate) fn add_nonmintable_trail(&mut self, trail_id: &TrailId) {
         self.nonmintable_trails.insert(trail_id);
     }
+
+    // Sets the total supply of a trail, so `is_mintable` only tracks whether copies are left.
+    // User purchases stay disabled for trails in `nonmintable_trails`, which is checked separately.
+    // Returns the previous total along with the updated trail.
+    pub(crate) fn internal_set_trail_supply(
+        &mut self,
+        trail_id: &TrailId,
+        total: u64,
+    ) -> (u64, TrailSeries) {
+        let mut trail = self.get_trail_by_id(trail_id);
+
+        require(
+            trail.creator_id == env::predecessor_account_id(),
+            CampgroundError::NotTrailCreator("change its supply"),
+        );
+        require(
+            total >= trail.supply.circulating,
+            CampgroundError::SupplyBelowCirculating,
+        );
+
+        let previous_total = trail.supply.total;
+        trail.supply.total = total;
+        trail.is_mintable = total > trail.supply.circulating;
+
+        self.trails_metadata_by_id.insert(trail_id, &trail);
+
+        (previous_total, trail)
+    }
+
+    // Hands out the next numeric trail ID
+    pub(crate) fn next_trail_id(&mut self) -> TrailId {
+        self.last_trail_id += 1;
+        self.last_trail_id.to_string()
+    }
+
+    // Trails and tokens are not all in the current layout until `migrate_v4_batch` completes
+    pub(crate) fn panic_if_migrating(&self) {
+        if self.migration.is_some() {
+            CampgroundError::MigrationInProgress.panic()
+        }
+    }
+
+    // Gets a trail with its `is_frozen` and `is_archived` flags filled in
+    pub(crate) fn internal_get_trail(&self, trail_id: &TrailId) -> Option<TrailSeries> {
+        self.panic_if_migrating();
+        self.trails_metadata_by_id.get(trail_id).map(|mut trail| {
+            trail.is_frozen = self.frozen_trails.contains(trail_id);
+            trail.is_archived = self.archived_trails.contains(trail_id);
+            trail
+        })
+    }
+
+    // Every path that changes the metadata or resources of a trail must go through this check
+    pub(crate) fn panic_if_trail_frozen(&self, trail_id: &TrailId) {
+        if self.frozen_trails.contains(trail_id) {
+            CampgroundError::TrailFrozen.panic()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,17 +1,26 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::collections::{
+    LazyOption, LookupMap, LookupSet, TreeMap, UnorderedMap, UnorderedSet,
+};
 use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     env, near_bindgen, AccountId, Balance, CryptoHash, PanicOnDefault, Promise, PromiseOrValue,
 };
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 // pub use crate::approval::*;
+use crate::admin::PendingAdminChange;
+use crate::config::Config;
+use crate::error::{require, CampgroundError};
 use crate::internal::*;
-pub use crate::market::*;
 pub use crate::metadata::*;
+use crate::migration::Migration;
 pub use crate::nft_core::*;
+use crate::pause::PausableOperation;
+use crate::roles::Role;
+use crate::vars::{SUB_ADMIN_ADDRESS, WHITELISTED_ADDRESS_MINTING_KEY};
 // pub use crate::royalty::*;
 // mod approval;
 pub mod bridge;
@@ -24,14 +33,35 @@ pub mod nft_core;
 // mod royalty;
 
 pub mod admin;
+pub mod config;
+pub mod error;
 pub mod event;
-pub mod vars;
+pub mod migration;
+pub mod pause;
+pub mod roles;
+pub mod storage;
 pub mod sub_admin;
+pub mod vars;
 
 pub const TRAIL_DELIMETER: char = ':';
 pub const ONE_NEAR: Balance = 1000000000000000000000000;
 pub const BUY_STORAGE: Balance = 6920000000000000000000;
 pub const MAX_PRICE: Balance = 1_000_000_000 * 10u128.pow(24);
+pub const MAX_SLUG_LENGTH: usize = 64;
+/// Media shown for trails without any, until `FallbackMedia` is configured.
+pub const DEFAULT_FALLBACK_MEDIA: &str = "https://assets.website-files.com/6183638541c07be9ecbe4559/6221296e8a95113bac90ffdd_transparent%20background.png";
+pub const MAX_CAMPGROUND_MINIMUM_FEE: Balance = 10 * ONE_NEAR;
+pub const MAX_CAMPGROUND_ID_LENGTH: usize = 64;
+/// Longest account id allowed by NEAR.
+pub const MAX_ACCOUNT_ID_LENGTH: usize = 64;
+/// Upper bound for every metadata size limit in the configuration.
+pub const MAX_METADATA_LIMIT: u32 = 65_536;
+/// Schemes allowed in metadata URIs, relative paths are resolved against `base_uri` instead.
+pub const ALLOWED_URI_SCHEMES: [&str; 5] = ["https", "http", "ipfs", "ar", "data"];
+/// Default delay for admin changes, 2 days in nanoseconds.
+pub const DEFAULT_ADMIN_CHANGE_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+/// Upper bound for the admin change delay, 30 days in nanoseconds.
+pub const MAX_ADMIN_CHANGE_DELAY: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
 
 /// Holds the state for the ITO (Initial Trail Offering) Smart Contract.
 #[near_bindgen]
@@ -40,9 +70,18 @@ pub struct Contract {
     /// Represents the owner of the contract.
     pub owner_id: AccountId,
 
+    /// Account nominated by the owner to become the new owner, if any.
+    pub pending_owner_id: Option<AccountId>,
+
     /// Keeps track of all the token IDs for a given account.
     pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TrailIdAndCopyNumber>>,
 
+    /// Keeps track of all the token IDs for a given account, ordered by mint index.
+    pub tokens_per_owner_by_mint: LookupMap<AccountId, TreeMap<u64, TrailIdAndCopyNumber>>,
+
+    /// Position of each token in mint order, starting at 0.
+    pub token_mint_index: LookupMap<TrailIdAndCopyNumber, u64>,
+
     /// Keeps track of the token struct for a given token ID.
     pub tokens_by_id: LookupMap<TrailIdAndCopyNumber, TrailBusiness>,
 
@@ -55,8 +94,46 @@ pub struct Contract {
     /// Keeps track of the token created by creator, represented by `AccountId`.
     pub trails_series_by_creator: LookupMap<AccountId, UnorderedSet<TrailId>>,
 
+    /// Keeps track of all the token IDs minted for a given trail series.
+    pub tokens_per_series: LookupMap<TrailId, UnorderedSet<TrailIdAndCopyNumber>>,
+
+    /// Keeps track of how many copies of a trail series each holder owns.
+    pub holders_per_series: LookupMap<TrailId, UnorderedMap<AccountId, u64>>,
+
+    /// Keeps track of how many copies of each trail series an account owns.
+    /// Inverse of `holders_per_series`, to list owners' trails without loading their copies.
+    pub series_per_owner: LookupMap<AccountId, UnorderedMap<TrailId, u64>>,
+
     pub nonmintable_trails: LookupSet<TrailId>,
 
+    /// Trails whose metadata has been permanently frozen by their creator.
+    pub frozen_trails: LookupSet<TrailId>,
+
+    /// Trails hidden from listings by their creator.
+    pub archived_trails: LookupSet<TrailId>,
+
+    /// When the metadata of each trail was last updated, Unix epoch in milliseconds.
+    /// Trails never updated are left out.
+    pub trails_updated_at: LookupMap<TrailId, u64>,
+
+    /// Last numeric `TrailId` handed out, never decreases even if trails are deleted.
+    pub last_trail_id: u64,
+
+    /// Keeps track of the accounts granted each `Role`.
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+
+    /// Operations currently paused contract-wide.
+    pub paused_operations: UnorderedSet<PausableOperation>,
+
+    /// Delay, in nanoseconds, before a queued admin change can be executed.
+    pub admin_change_delay: u64,
+
+    /// Fee and treasury changes waiting for `admin_change_delay` to pass.
+    pub pending_admin_changes: UnorderedMap<u64, PendingAdminChange>,
+
+    /// Last id handed out to an admin change.
+    pub last_admin_change_id: u64,
+
     /// Represents the metadata for the contract.
     pub metadata: LazyOption<NFTContractMetadata>,
 
@@ -68,7 +145,11 @@ pub struct Contract {
 
     pub campground_minimum_fee_yocto_near: Balance,
 
-    pub settings: UnorderedMap<String, String>,
+    /// Typed contract configuration, see `ConfigKey`.
+    pub config: Config,
+
+    /// Cursor of the reindex started by `migrate_v3_to_v4`, until `migrate_v4_batch` completes it.
+    pub migration: Option<Migration>,
 }
 
 /// Helper structure for keys of the persistent collections.
@@ -84,6 +165,23 @@ pub enum StorageKey {
     TokensPerType,
     TokensPerTypeInner { token_type_hash: CryptoHash },
     TokenTypesLocked,
+    FrozenTrails,
+    ArchivedTrails,
+    RoleMembers,
+    RoleMembersInner { role: Role },
+    PausedOperations,
+    PendingAdminChanges,
+    // Unused, as the configuration is stored inline in the contract state
+    Config,
+    TokenPerCreatorInner { account_id_hash: CryptoHash },
+    HoldersPerSeries,
+    HoldersPerSeriesInner { trail_id_hash: CryptoHash },
+    TokensPerOwnerByMint,
+    TokensPerOwnerByMintInner { account_id_hash: CryptoHash },
+    TokenMintIndex,
+    TrailsUpdatedAt,
+    SeriesPerOwner,
+    SeriesPerOwnerInner { account_id_hash: CryptoHash },
 }
 
 #[derive(BorshSerialize)]
@@ -96,6 +194,62 @@ pub enum StorageKeysV3 {
     NonMintableTrails,
 }
 
+/// Series metadata layout up to `v3`, without `media_hash` nor `fallback_media`.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TrailSeriesMetadataV3 {
+    title: String,
+    description: String,
+    tickets_amount: u64,
+    media: Option<String>,
+    data: Option<String>,
+    resources: Vec<TrailResource>,
+    starts_at: Option<u64>,
+    expires_at: Option<u64>,
+    reference: Option<String>,
+    campground_id: String,
+}
+
+/// Series layout up to `v3`.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TrailSeriesV3 {
+    is_mintable: bool,
+    creator_id: AccountId,
+    issue_at: u64,
+    metadata: TrailSeriesMetadataV3,
+    supply: SeriesSupply,
+    price: U128,
+    campground_fee_near: U128,
+    creator_royalty_near: Option<U128>,
+    royalties: HashMap<AccountId, u32>,
+}
+
+/// Token layout up to `v3`, with a copy of the series metadata in every token.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TrailBusinessV3 {
+    owner_id: AccountId,
+    token_id: TrailId,
+    partial_metadata: TokenMetadata,
+}
+
+/// Contract state layout up to `v3`.
+/// Every migration goes through this layout, so new fields only need to be
+/// initialized in `Contract::from_v3`.
+#[derive(BorshDeserialize)]
+struct CampgroundContractV3 {
+    owner_id: AccountId,
+    tokens_per_owner: LookupMap<AccountId, UnorderedSet<TrailIdAndCopyNumber>>,
+    tokens_by_id: LookupMap<TrailIdAndCopyNumber, TrailBusinessV3>,
+    token_metadata_by_id: UnorderedMap<TrailIdAndCopyNumber, TrailId>,
+    trails_metadata_by_id: UnorderedMap<TrailId, TrailSeriesV3>,
+    trails_series_by_creator: LookupMap<AccountId, UnorderedSet<TrailId>>,
+    nonmintable_trails: LookupSet<TrailId>,
+    metadata: LazyOption<NFTContractMetadata>,
+    campground_fee: u64,
+    campground_treasury_address: AccountId,
+    campground_minimum_fee_yocto_near: Balance,
+    settings: UnorderedMap<String, String>,
+}
+
 #[near_bindgen]
 impl Contract {
     /// Initialization function (can only be called once).
@@ -128,6 +282,10 @@ impl Contract {
         let this = Self {
             //Storage keys are simply the prefixes used for the collections. This helps avoid data collision
             tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
+            tokens_per_owner_by_mint: LookupMap::new(
+                StorageKey::TokensPerOwnerByMint.try_to_vec().unwrap(),
+            ),
+            token_mint_index: LookupMap::new(StorageKey::TokenMintIndex.try_to_vec().unwrap()),
             tokens_by_id: LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
             token_metadata_by_id: UnorderedMap::new(
                 StorageKey::TokenMetadataById.try_to_vec().unwrap(),
@@ -137,6 +295,7 @@ impl Contract {
             ),
             //set the owner_id field equal to the passed in owner_id.
             owner_id,
+            pending_owner_id: None,
             metadata: LazyOption::new(
                 StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
                 Some(&metadata),
@@ -147,16 +306,106 @@ impl Contract {
             trails_series_by_creator: LookupMap::new(
                 StorageKey::TokenPerCreator.try_to_vec().unwrap(),
             ),
-            settings: UnorderedMap::new(StorageKeysV2::Settings.try_to_vec().unwrap()),
+            tokens_per_series: LookupMap::new(StorageKey::TokensPerType.try_to_vec().unwrap()),
+            holders_per_series: LookupMap::new(StorageKey::HoldersPerSeries.try_to_vec().unwrap()),
+            series_per_owner: LookupMap::new(StorageKey::SeriesPerOwner.try_to_vec().unwrap()),
+            config: Config::default(),
             nonmintable_trails: LookupSet::new(
                 StorageKeysV3::NonMintableTrails.try_to_vec().unwrap(),
             ),
+            frozen_trails: LookupSet::new(StorageKey::FrozenTrails.try_to_vec().unwrap()),
+            archived_trails: LookupSet::new(StorageKey::ArchivedTrails.try_to_vec().unwrap()),
+            trails_updated_at: LookupMap::new(StorageKey::TrailsUpdatedAt.try_to_vec().unwrap()),
+            last_trail_id: 0,
+            role_members: LookupMap::new(StorageKey::RoleMembers.try_to_vec().unwrap()),
+            paused_operations: UnorderedSet::new(
+                StorageKey::PausedOperations.try_to_vec().unwrap(),
+            ),
+            admin_change_delay: DEFAULT_ADMIN_CHANGE_DELAY,
+            pending_admin_changes: UnorderedMap::new(
+                StorageKey::PendingAdminChanges.try_to_vec().unwrap(),
+            ),
+            last_admin_change_id: 0,
+            migration: None,
         };
 
         //return the Contract object
         this
     }
 
+    /// Builds the current state from the `v3` layout,
+    /// initializing every field added afterwards.
+    /// Trails and tokens keep their `v3` layout until `migrate_v4_batch` rewrites and indexes them.
+    fn from_v3(state: CampgroundContractV3) -> Self {
+        // Up to `v3`, trails could not be deleted and were numbered `1..=len`.
+        let last_trail_id = state.trails_metadata_by_id.len();
+        let mut settings = state.settings;
+
+        // Same prefixes, only the layout of the values changes
+        let tokens_by_id =
+            LookupMap::try_from_slice(&state.tokens_by_id.try_to_vec().unwrap()).unwrap();
+        let trails_metadata_by_id =
+            UnorderedMap::try_from_slice(&state.trails_metadata_by_id.try_to_vec().unwrap())
+                .unwrap();
+
+        let mut this = Self {
+            owner_id: state.owner_id,
+            pending_owner_id: None,
+            tokens_per_owner: state.tokens_per_owner,
+            tokens_per_owner_by_mint: LookupMap::new(
+                StorageKey::TokensPerOwnerByMint.try_to_vec().unwrap(),
+            ),
+            token_mint_index: LookupMap::new(StorageKey::TokenMintIndex.try_to_vec().unwrap()),
+            tokens_by_id,
+            token_metadata_by_id: state.token_metadata_by_id,
+            trails_metadata_by_id,
+            trails_series_by_creator: state.trails_series_by_creator,
+            tokens_per_series: LookupMap::new(StorageKey::TokensPerType.try_to_vec().unwrap()),
+            holders_per_series: LookupMap::new(StorageKey::HoldersPerSeries.try_to_vec().unwrap()),
+            series_per_owner: LookupMap::new(StorageKey::SeriesPerOwner.try_to_vec().unwrap()),
+            nonmintable_trails: state.nonmintable_trails,
+            metadata: state.metadata,
+            campground_fee: state.campground_fee,
+            campground_treasury_address: state.campground_treasury_address,
+            campground_minimum_fee_yocto_near: state.campground_minimum_fee_yocto_near,
+            config: Config::default(),
+            frozen_trails: LookupSet::new(StorageKey::FrozenTrails.try_to_vec().unwrap()),
+            archived_trails: LookupSet::new(StorageKey::ArchivedTrails.try_to_vec().unwrap()),
+            trails_updated_at: LookupMap::new(StorageKey::TrailsUpdatedAt.try_to_vec().unwrap()),
+            last_trail_id,
+            role_members: LookupMap::new(StorageKey::RoleMembers.try_to_vec().unwrap()),
+            paused_operations: UnorderedSet::new(
+                StorageKey::PausedOperations.try_to_vec().unwrap(),
+            ),
+            admin_change_delay: DEFAULT_ADMIN_CHANGE_DELAY,
+            pending_admin_changes: UnorderedMap::new(
+                StorageKey::PendingAdminChanges.try_to_vec().unwrap(),
+            ),
+            last_admin_change_id: 0,
+            migration: Some(Migration::new()),
+        };
+
+        // Up to `v3`, the sub-admin and the fiat minter were kept in free-form `settings`.
+        // No other setting was ever read, so the remaining entries are dropped
+        // rather than mapped to a `ConfigKey`.
+        for (key, value) in settings.iter() {
+            let role = match key.as_str() {
+                SUB_ADMIN_ADDRESS => Some(Role::SubAdmin),
+                WHITELISTED_ADDRESS_MINTING_KEY => Some(Role::FiatMinter),
+                _ => None,
+            };
+            match (role, AccountId::try_from(value)) {
+                (Some(role), Ok(account_id)) => {
+                    this.internal_grant_role(role, &account_id);
+                }
+                _ => env::log_str(&format!("Campground: Dropping setting `{}`", key)),
+            }
+        }
+        settings.clear();
+
+        this
+    }
+
     #[private]
     #[init(ignore_state)]
     pub fn migrate_v1_to_v2() -> Self {
@@ -164,9 +413,9 @@ impl Contract {
         pub struct CampgroundContractV1 {
             pub owner_id: AccountId,
             pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TrailIdAndCopyNumber>>,
-            pub tokens_by_id: LookupMap<TrailIdAndCopyNumber, TrailBusiness>,
+            pub tokens_by_id: LookupMap<TrailIdAndCopyNumber, TrailBusinessV3>,
             pub token_metadata_by_id: UnorderedMap<TrailIdAndCopyNumber, TrailId>,
-            pub trails_metadata_by_id: UnorderedMap<TrailId, TrailSeries>,
+            pub trails_metadata_by_id: UnorderedMap<TrailId, TrailSeriesV3>,
             pub trails_series_by_creator: LookupMap<AccountId, UnorderedSet<TrailId>>,
             pub metadata: LazyOption<NFTContractMetadata>,
             pub campground_fee: u64,
@@ -176,7 +425,7 @@ impl Contract {
 
         let state: CampgroundContractV1 = env::state_read().unwrap();
 
-        Self {
+        Self::from_v3(CampgroundContractV3 {
             owner_id: state.owner_id,
             tokens_per_owner: state.tokens_per_owner,
             tokens_by_id: state.tokens_by_id,
@@ -191,7 +440,7 @@ impl Contract {
             nonmintable_trails: LookupSet::new(
                 StorageKeysV3::NonMintableTrails.try_to_vec().unwrap(),
             ),
-        }
+        })
     }
 
     #[private]
@@ -201,9 +450,9 @@ impl Contract {
         pub struct CampgroundContractV2 {
             pub owner_id: AccountId,
             pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TrailIdAndCopyNumber>>,
-            pub tokens_by_id: LookupMap<TrailIdAndCopyNumber, TrailBusiness>,
+            pub tokens_by_id: LookupMap<TrailIdAndCopyNumber, TrailBusinessV3>,
             pub token_metadata_by_id: UnorderedMap<TrailIdAndCopyNumber, TrailId>,
-            pub trails_metadata_by_id: UnorderedMap<TrailId, TrailSeries>,
+            pub trails_metadata_by_id: UnorderedMap<TrailId, TrailSeriesV3>,
             pub trails_series_by_creator: LookupMap<AccountId, UnorderedSet<TrailId>>,
             pub metadata: LazyOption<NFTContractMetadata>,
             pub campground_fee: u64,
@@ -214,7 +463,7 @@ impl Contract {
 
         let state: CampgroundContractV2 = env::state_read().unwrap();
 
-        Self {
+        Self::from_v3(CampgroundContractV3 {
             owner_id: state.owner_id,
             tokens_per_owner: state.tokens_per_owner,
             tokens_by_id: state.tokens_by_id,
@@ -229,6 +478,16 @@ impl Contract {
             nonmintable_trails: LookupSet::new(
                 StorageKeysV3::NonMintableTrails.try_to_vec().unwrap(),
             ),
-        }
+        })
+    }
+
+    /// Only migrates the contract state itself,
+    /// `migrate_v4_batch` must then be called until it returns `true`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate_v3_to_v4() -> Self {
+        let state: CampgroundContractV3 = env::state_read().unwrap();
+
+        Self::from_v3(state)
     }
 }
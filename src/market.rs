@@ -1,5 +1,6 @@
 use crate::bridge::SeriesBridge;
-use crate::event::NearEvent;
+use crate::event::{NearEvent, PaymentMethod, PurchaseData};
+use crate::pause::PausableOperation;
 use crate::*;
 
 #[near_bindgen]
@@ -11,17 +12,15 @@ impl Contract {
     ) -> TrailIdAndCopyNumber {
         let mut token_series = self.get_trail_by_id(&series_id);
 
-        assert!(
-            token_series.is_mintable,
-            "Campground: Trail is not mintable"
-        );
+        require(token_series.is_mintable, CampgroundError::TrailNotMintable);
+        require(!token_series.is_archived, CampgroundError::TrailArchived);
 
         let max_supply = token_series.supply.total;
         let mut circulating_supply = token_series.supply.circulating;
-        // 10 (max) > 1 (circulating) = true
-        assert!(
-            max_supply > circulating_supply || max_supply == circulating_supply,
-            "Campground: No more minting allowed"
+        // 10 (max) > 9 (circulating) = true, 10 (max) > 10 (circulating) = false
+        require(
+            max_supply > circulating_supply,
+            CampgroundError::TrailSoldOut,
         );
 
         circulating_supply += 1;
@@ -39,24 +38,30 @@ impl Contract {
         let token = TrailBusiness {
             owner_id: receiver_id.clone(),
             token_id: series_id.to_owned(),
-            partial_metadata: partial_metadata_from_trail_series(&token_series),
+            copy_number: circulating_supply,
+            issued_at: Some(env::block_timestamp() / 1_000_000),
         };
 
         //insert the token ID and token struct and make sure that the token doesn't exist
-        assert!(
+        require(
             self.tokens_by_id.insert(&ownership_id, &token).is_none(),
-            "Trail copy already exists"
+            CampgroundError::TrailCopyAlreadyExists,
         );
 
-        assert!(
+        require(
             self.token_metadata_by_id
                 .insert(&ownership_id, &series_id)
                 .is_none(),
-            "Trail copy might already exist"
+            CampgroundError::TrailCopyAlreadyExists,
         );
 
+        //tokens are never removed from `token_metadata_by_id`, so its length gives the mint order
+        self.token_mint_index
+            .insert(&ownership_id, &(self.token_metadata_by_id.len() - 1));
+
         //call the internal method for adding the token to the owner
         self.internal_add_trail_to_owner(&token.owner_id, &ownership_id);
+        self.internal_add_trail_to_series(&series_id, &ownership_id);
 
         let price = &token_series.price.clone();
 
@@ -75,7 +80,7 @@ impl Contract {
         let trail_series = self
             .trails_metadata_by_id
             .get(&trail_series_id)
-            .expect("Campground: Trail series does not exist");
+            .unwrap_or_else(|| CampgroundError::TrailNotFound.panic());
         U128(get_price_and_fee(&trail_series).0)
     }
 
@@ -86,30 +91,29 @@ impl Contract {
         trail_series_id: TrailId,
         receiver_id: AccountId,
     ) -> TrailIdAndCopyNumber {
+        self.panic_if_paused(PausableOperation::Buy);
+
         let trail_series = self
             .trails_metadata_by_id
             .get(&trail_series_id)
-            .expect("Campground: Trail series does not exist");
+            .unwrap_or_else(|| CampgroundError::TrailNotFound.panic());
         let (price, fee) = get_price_and_fee(&trail_series);
         let attached_deposit = env::attached_deposit();
 
         // TODO: Refunds ?
-        assert!(
+        require(
             self.is_trail_mintable(&trail_series_id),
-            "Campground: Trail is not allowed to be minted by user"
+            CampgroundError::TrailNotMintableByUser,
         );
 
-        assert_eq!(
-            attached_deposit, price,
-            "Campground: Attached deposit needs to be equal to ITO price or Campground Fee"
-        );
+        require(attached_deposit == price, CampgroundError::WrongDeposit);
 
         // If for_treasury <= campground_minimum_fee_yocto_near, the buyer pays the fees
         // Otherwise, the seller pays the fee (price - for_treasury)
         let price_after_fee = price - fee;
 
         let trail_id_with_copy: TrailIdAndCopyNumber =
-            self.nft_internal_mint_series(trail_series_id, receiver_id);
+            self.nft_internal_mint_series(trail_series_id.clone(), receiver_id.clone());
 
         if price_after_fee > 0 {
             Promise::new(trail_series.creator_id).transfer(price_after_fee);
@@ -117,32 +121,59 @@ impl Contract {
 
         Promise::new(self.campground_treasury_address.clone()).transfer(fee);
 
+        NearEvent::log_series_purchase(PurchaseData {
+            buyer_id: env::predecessor_account_id().to_string(),
+            receiver_id: receiver_id.to_string(),
+            series_id: trail_series_id,
+            token_id: trail_id_with_copy.clone(),
+            amount: price.into(),
+            campground_fee: fee.into(),
+            creator_proceeds: price_after_fee.into(),
+            payment_method: PaymentMethod::Near,
+            storage_deposit: 0.into(),
+            refund: 0.into(),
+        });
+
         trail_id_with_copy
     }
 
     #[payable]
     pub fn nft_mint(&mut self, token_id: TrailId, receiver_id: AccountId) -> TrailIdAndCopyNumber {
+        self.panic_if_paused(PausableOperation::Mint);
+
         //measure the initial storage being used on the contract
         let initial_storage_usage = env::storage_usage();
 
         let token_series = self.get_trail_by_id(&token_id);
 
-        let predecessor = env::predecessor_account_id();
-
-        assert!(
-            predecessor == token_series.creator_id
-                || self.is_there_whitelisted_address()
-                    && predecessor == self.get_whitelisted_address(),
-            "Campground: Only Trail creator can directly mint"
-        );
+        let payment_method = if env::predecessor_account_id() == token_series.creator_id {
+            PaymentMethod::CreatorMinted
+        } else {
+            self.panic_if_not_fiat_minter();
+            PaymentMethod::FiatMinted
+        };
 
-        let trail_mint_id = self.nft_internal_mint_series(token_id, receiver_id);
+        let trail_mint_id = self.nft_internal_mint_series(token_id.clone(), receiver_id.clone());
 
         //calculate the required storage which was the used - initial
         let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
 
         //refund any excess storage if the user attached too much. Panic if they didn't attach enough to cover the required.
-        refund_deposit(required_storage_in_bytes, 0);
+        let refund = refund_deposit(required_storage_in_bytes, 0);
+
+        // Copies minted directly are paid off-chain, the deposit only covers storage
+        NearEvent::log_series_purchase(PurchaseData {
+            buyer_id: env::predecessor_account_id().to_string(),
+            receiver_id: receiver_id.to_string(),
+            series_id: token_id,
+            token_id: trail_mint_id.clone(),
+            amount: 0.into(),
+            campground_fee: 0.into(),
+            creator_proceeds: 0.into(),
+            payment_method,
+            storage_deposit: env::attached_deposit().into(),
+            refund: refund.into(),
+        });
 
         trail_mint_id
     }
@@ -152,7 +183,7 @@ impl Contract {
 /// of the given `trail_series`.
 /// The first component represents the price to be payed to buy the nft.
 /// The second components represents the Campground fee.
-fn get_price_and_fee(trail_series: &TrailSeries) -> (u128, u128) {
+pub(crate) fn get_price_and_fee(trail_series: &TrailSeries) -> (u128, u128) {
     if trail_series.price.0 > trail_series.campground_fee_near.0 {
         (trail_series.price.0, trail_series.campground_fee_near.0)
     } else {
@@ -54,6 +54,7 @@ pub struct SeriesSupply {
 pub struct TrailSeriesMetadata {
     pub title: String,
     pub description: String,
+    /// Supply the trail was created with, `supply.total` holds the current one
     pub tickets_amount: u64,
     pub media: Option<String>,
     pub data: Option<String>,
@@ -65,6 +66,12 @@ pub struct TrailSeriesMetadata {
     /// Url referencing something of this resource,
     pub reference: Option<String>,
     pub campground_id: String,
+    /// Base64-encoded sha256 hash of the content referenced by `media`.
+    #[serde(default)]
+    pub media_hash: Option<Base64VecU8>,
+    /// Media shown when `media` is not set, instead of the `FallbackMedia` configuration.
+    #[serde(default)]
+    pub fallback_media: Option<String>,
 }
 
 /// As defined in NEP-177.
@@ -109,6 +116,16 @@ pub struct TrailSeries {
     pub campground_fee_near: U128,
     pub creator_royalty_near: Option<U128>,
     pub royalties: HashMap<AccountId, u32>,
+    /// Whether the creator has permanently frozen the metadata of this series.
+    /// Not stored along the series, it is filled in from `frozen_trails` on read.
+    #[borsh_skip]
+    #[serde(default)]
+    pub is_frozen: bool,
+    /// Whether the creator has archived this series, hiding it from listings.
+    /// Not stored along the series, it is filled in from `archived_trails` on read.
+    #[borsh_skip]
+    #[serde(default)]
+    pub is_archived: bool,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -116,8 +133,13 @@ pub struct TrailSeries {
 pub struct TrailBusiness {
     /// Owner of the token
     pub owner_id: AccountId,
+    /// Trail series the copy belongs to.
     pub token_id: TrailId,
-    pub partial_metadata: TokenMetadata,
+    /// Copy number within the series, starting at 1.
+    pub copy_number: u64,
+    /// When the copy was minted, Unix epoch in milliseconds.
+    /// `None` for copies minted before it was recorded.
+    pub issued_at: Option<u64>,
 }
 
 /// The Json token is what will be returned from view calls.
@@ -134,6 +156,52 @@ pub struct JsonTrail {
     pub metadata: TokenMetadata,
 }
 
+/// Token as defined by NEP-171, returned by the standard `nft_*` views.
+/// `JsonTrail` is the Campground variant, along with the trail series.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonToken {
+    /// token ID
+    pub token_id: TrailIdAndCopyNumber,
+    /// owner of the token
+    pub owner_id: AccountId,
+    /// NEAR metadata
+    pub metadata: TokenMetadata,
+    /// Trails do not support approvals yet, so this is always empty.
+    pub approved_account_ids: HashMap<AccountId, u64>,
+}
+
+impl From<JsonTrail> for JsonToken {
+    fn from(trail: JsonTrail) -> Self {
+        Self {
+            token_id: trail.token_id,
+            owner_id: trail.owner_id,
+            metadata: trail.metadata,
+            approved_account_ids: HashMap::new(),
+        }
+    }
+}
+
+/// An account holding copies of a trail series.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesHolder {
+    /// holder of the copies
+    pub account_id: AccountId,
+    /// number of copies held
+    pub copies: u64,
+}
+
+/// A trail series along with its ID, as returned by paginated views.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTrailSeries {
+    /// trail ID
+    pub series_id: TrailId,
+    /// trail series
+    pub series: TrailSeries,
+}
+
 /// NEP-177 interface definition.
 ///
 /// See https://nomicon.io/Standards/Tokens/NonFungibleToken/Metadata.
@@ -0,0 +1,296 @@
+use crate::*;
+use std::cmp;
+
+/// Items processed by `migrate_v4_batch` when no `limit` is given.
+pub const DEFAULT_MIGRATION_BATCH: u64 = 100;
+
+/// Steps of the reindex started by `migrate_v3_to_v4`, run in this order.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MigrationStep {
+    /// Rewrites every trail series with `media_hash` and `fallback_media`.
+    Trails,
+    /// Detaches every creator from the set of trails they shared up to `v3`.
+    DetachCreators,
+    /// Clears the set of trails shared by every creator up to `v3`.
+    ClearSharedCreatorSet,
+    /// Indexes every trail series under its creator's own set.
+    IndexCreators,
+    /// Compacts every token and indexes it by mint order, trail series and holder.
+    Tokens,
+}
+
+impl MigrationStep {
+    fn next(self) -> Option<Self> {
+        use MigrationStep::*;
+        match self {
+            Trails => Some(DetachCreators),
+            DetachCreators => Some(ClearSharedCreatorSet),
+            ClearSharedCreatorSet => Some(IndexCreators),
+            IndexCreators => Some(Tokens),
+            Tokens => None,
+        }
+    }
+}
+
+/// Cursor of the reindex started by `migrate_v3_to_v4`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Migration {
+    /// Step being run.
+    pub step: MigrationStep,
+    /// Position of the next trail or token to process in `step`.
+    pub index: u64,
+    /// Longest copy found so far of the set of trails shared by every creator up to `v3`.
+    shared_creator_set: Option<UnorderedSet<TrailId>>,
+}
+
+impl Migration {
+    pub(crate) fn new() -> Self {
+        Self {
+            step: MigrationStep::Trails,
+            index: 0,
+            shared_creator_set: None,
+        }
+    }
+}
+
+/// Completes the migration from `v3` in batches,
+/// as reindexing every trail and token at once would not fit in a single transaction.
+///
+/// While the migration is in progress, every trail view and every pausable operation
+/// panics, as trails and tokens are not all in the current layout yet.
+/// Only the contract `owner` can run a batch, attaching exactly 1 yoctoNEAR.
+pub trait MigrationBridge {
+    /// Processes up to `limit` trails or tokens, `DEFAULT_MIGRATION_BATCH` by default,
+    /// resuming where the previous batch stopped.
+    /// Returns `true` once the migration is complete.
+    fn migrate_v4_batch(&mut self, limit: Option<u64>) -> bool;
+
+    /// Returns the step being run, or `None` if no migration is in progress.
+    fn get_migration_step(&self) -> Option<MigrationStep>;
+}
+
+#[near_bindgen]
+impl MigrationBridge for Contract {
+    #[payable]
+    fn migrate_v4_batch(&mut self, limit: Option<u64>) -> bool {
+        assert_one_yocto();
+        self.panic_if_not_owner();
+
+        let mut migration = match self.migration.take() {
+            Some(migration) => migration,
+            None => return true,
+        };
+
+        let mut remaining = limit.unwrap_or(DEFAULT_MIGRATION_BATCH);
+        loop {
+            let pending = self.pending_in_step(&migration);
+            if pending == 0 {
+                match migration.step.next() {
+                    Some(step) => {
+                        migration.step = step;
+                        migration.index = 0;
+                        continue;
+                    }
+                    None => {
+                        env::log_str("Campground: Migration completed");
+                        return true;
+                    }
+                }
+            }
+            if remaining == 0 {
+                break;
+            }
+
+            let count = cmp::min(pending, remaining);
+            self.run_step(&mut migration, count);
+            migration.index += count;
+            remaining -= count;
+        }
+
+        env::log_str(&format!(
+            "Campground: Migration paused at `{:?}` {}",
+            migration.step, migration.index
+        ));
+        self.migration = Some(migration);
+        false
+    }
+
+    fn get_migration_step(&self) -> Option<MigrationStep> {
+        self.migration.as_ref().map(|migration| migration.step)
+    }
+}
+
+impl Contract {
+    /// Trails or tokens left to process in the current step.
+    fn pending_in_step(&self, migration: &Migration) -> u64 {
+        use MigrationStep::*;
+        match migration.step {
+            Trails | DetachCreators | IndexCreators => {
+                self.trails_metadata_by_id.len() - migration.index
+            }
+            ClearSharedCreatorSet => migration
+                .shared_creator_set
+                .as_ref()
+                .map_or(0, |shared_set| shared_set.len()),
+            Tokens => self.token_metadata_by_id.len() - migration.index,
+        }
+    }
+
+    /// Processes the next `count` trails or tokens of the current step.
+    fn run_step(&mut self, migration: &mut Migration, count: u64) {
+        let range = migration.index..migration.index + count;
+        match migration.step {
+            MigrationStep::Trails => self.upgrade_trails(range),
+            MigrationStep::DetachCreators => {
+                for index in range {
+                    let creator_id = self.migrated_trail_creator(index);
+                    // Each creator kept its own copy of the shared set, as it was when last saved.
+                    // Trails were never removed up to `v3`, so the longest copy is the up to date one.
+                    if let Some(set) = self.trails_series_by_creator.remove(&creator_id) {
+                        let longest = match &migration.shared_creator_set {
+                            Some(shared_set) => set.len() > shared_set.len(),
+                            None => true,
+                        };
+                        if longest {
+                            migration.shared_creator_set = Some(set);
+                        }
+                    }
+                }
+            }
+            MigrationStep::ClearSharedCreatorSet => {
+                let shared_set = migration.shared_creator_set.as_mut().unwrap();
+                for _ in range {
+                    // Removing the last element does not move any other
+                    let last = shared_set.as_vector().get(shared_set.len() - 1).unwrap();
+                    shared_set.remove(&last);
+                }
+            }
+            MigrationStep::IndexCreators => {
+                for index in range {
+                    let trail_id = self
+                        .trails_metadata_by_id
+                        .keys_as_vector()
+                        .get(index)
+                        .unwrap();
+                    let creator_id = self.migrated_trail_creator(index);
+                    self.internal_add_trail_to_creator(&creator_id, &trail_id);
+                }
+            }
+            MigrationStep::Tokens => self.compact_tokens(range),
+        }
+    }
+
+    /// Creator of the trail series at `index`, once rewritten by `MigrationStep::Trails`.
+    fn migrated_trail_creator(&self, index: u64) -> AccountId {
+        self.trails_metadata_by_id
+            .values_as_vector()
+            .get(index)
+            .unwrap()
+            .creator_id
+    }
+
+    /// Up to `v3`, series metadata had no `media_hash` nor `fallback_media`.
+    /// Rewrites the series in `range` in place, keeping the creation order.
+    fn upgrade_trails(&mut self, range: std::ops::Range<u64>) {
+        // Same prefixes, only the layout of the values changes
+        let mut legacy_trails: UnorderedMap<TrailId, TrailSeriesV3> =
+            UnorderedMap::try_from_slice(&self.trails_metadata_by_id.try_to_vec().unwrap())
+                .unwrap();
+        for index in range {
+            let trail_id = legacy_trails.keys_as_vector().get(index).unwrap();
+            let legacy = legacy_trails.values_as_vector().get(index).unwrap();
+            let metadata = legacy.metadata;
+            let trail = TrailSeries {
+                is_mintable: legacy.is_mintable,
+                creator_id: legacy.creator_id,
+                issue_at: legacy.issue_at,
+                metadata: TrailSeriesMetadata {
+                    title: metadata.title,
+                    description: metadata.description,
+                    tickets_amount: metadata.tickets_amount,
+                    media: metadata.media,
+                    data: metadata.data,
+                    resources: metadata.resources,
+                    starts_at: metadata.starts_at,
+                    expires_at: metadata.expires_at,
+                    reference: metadata.reference,
+                    campground_id: metadata.campground_id,
+                    media_hash: None,
+                    fallback_media: None,
+                },
+                supply: legacy.supply,
+                price: legacy.price,
+                campground_fee_near: legacy.campground_fee_near,
+                creator_royalty_near: legacy.creator_royalty_near,
+                royalties: legacy.royalties,
+                is_frozen: false,
+                is_archived: false,
+            };
+            // Raw, as `insert` would read the previous value with the new layout
+            legacy_trails.insert_raw(
+                &trail_id.try_to_vec().unwrap(),
+                &trail.try_to_vec().unwrap(),
+            );
+        }
+    }
+
+    /// Up to `v3`, every token stored a copy of its series metadata, which is now derived on read.
+    /// Rewrites the tokens in `range` in place without it, logging the bytes freed,
+    /// and indexes them by mint order, trail series and holder.
+    /// Tokens were never removed, so `token_metadata_by_id` still lists them in mint order.
+    fn compact_tokens(&mut self, range: std::ops::Range<u64>) {
+        // Same prefix, only the layout of the values changes
+        let mut legacy_tokens: LookupMap<TrailIdAndCopyNumber, TrailBusinessV3> =
+            LookupMap::try_from_slice(&self.tokens_by_id.try_to_vec().unwrap()).unwrap();
+
+        let mut compacted = 0;
+        let mut freed = 0;
+        for mint_index in range {
+            let token_id = self
+                .token_metadata_by_id
+                .keys_as_vector()
+                .get(mint_index)
+                .unwrap();
+            let series_id = self
+                .token_metadata_by_id
+                .values_as_vector()
+                .get(mint_index)
+                .unwrap();
+
+            let initial_storage_usage = env::storage_usage();
+            // Removed first, as `insert` would read the previous value with the new layout
+            if let Some(legacy) = legacy_tokens.remove(&token_id) {
+                let copy_number = get_id_and_copy(token_id.clone())
+                    .1
+                    .parse()
+                    .unwrap_or_else(|_| CampgroundError::MalformedTrailCopyId.panic());
+                self.tokens_by_id.insert(
+                    &token_id,
+                    &TrailBusiness {
+                        owner_id: legacy.owner_id,
+                        token_id: legacy.token_id,
+                        copy_number,
+                        issued_at: None,
+                    },
+                );
+                compacted += 1;
+                freed += initial_storage_usage.saturating_sub(env::storage_usage());
+            }
+
+            self.token_mint_index.insert(&token_id, &mint_index);
+            self.internal_add_trail_to_series(&series_id, &token_id);
+            if let Some(token) = self.tokens_by_id.get(&token_id) {
+                self.internal_add_holder_copy(&series_id, &token.owner_id);
+                self.internal_add_trail_to_owner_by_mint(&token.owner_id, &token_id);
+            }
+        }
+
+        env::log_str(&format!(
+            "Campground: Compacted {} tokens, freeing {} bytes",
+            compacted, freed
+        ));
+    }
+}
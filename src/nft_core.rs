@@ -1,4 +1,9 @@
+// `ext_contract` expands `nft_resolve_transfer` with the promise arguments appended.
+#![allow(clippy::too_many_arguments)]
+
+use crate::bridge::SeriesBridge;
 use crate::event::NearEvent;
+use crate::pause::PausableOperation;
 use crate::*;
 use near_sdk::{ext_contract, Gas, PromiseResult};
 
@@ -9,6 +14,7 @@ const NO_DEPOSIT: Balance = 0;
 
 pub trait NonFungibleTokenCore {
     //transfers an NFT to a receiver ID
+    /// Requires exactly 1 yoctoNEAR attached, as per NEP-171.
     fn nft_transfer(
         &mut self,
         receiver_id: AccountId,
@@ -18,6 +24,7 @@ pub trait NonFungibleTokenCore {
 
     //transfers an NFT to a receiver and calls a function on the receiver ID's contract
     /// Returns `true` if the token was transferred from the sender's account.
+    /// Requires exactly 1 yoctoNEAR attached, as per NEP-171.
     fn nft_transfer_call(
         &mut self,
         receiver_id: AccountId,
@@ -27,7 +34,7 @@ pub trait NonFungibleTokenCore {
     ) -> PromiseOrValue<bool>;
 
     //get information about the NFT token passed in
-    fn nft_token(&self, token_id: TrailIdAndCopyNumber) -> Option<JsonTrail>;
+    fn nft_token(&self, token_id: TrailIdAndCopyNumber) -> Option<JsonToken>;
 }
 
 #[ext_contract(ext_non_fungible_token_receiver)]
@@ -52,14 +59,12 @@ trait NonFungibleTokenResolver {
     */
     fn nft_resolve_transfer(
         &mut self,
-        //we introduce an authorized ID for logging the transfer event
-        authorized_id: Option<String>,
         owner_id: AccountId,
         receiver_id: AccountId,
         token_id: TrailIdAndCopyNumber,
         //we introduce the approval map so we can keep track of what the approvals were before the transfer
         approved_account_ids: HashMap<AccountId, u64>,
-        //we introduce a memo for logging the transfer event
+        //only passed by callbacks scheduled before `v4`, kept so that they still decode
         memo: Option<String>,
     ) -> bool;
 }
@@ -69,17 +74,15 @@ trait NonFungibleTokenResolver {
     this is stored on THIS contract and is meant to analyze what happened in the cross contract call when nft_on_transfer was called
     as part of the nft_transfer_call method
 */
-trait NonFungibleTokenResolver {
+pub trait NonFungibleTokenResolver {
     fn nft_resolve_transfer(
         &mut self,
-        //we introduce an authorized ID for logging the transfer event
-        authorized_id: Option<String>,
         owner_id: AccountId,
         receiver_id: AccountId,
         token_id: TrailIdAndCopyNumber,
         //we introduce the approval map so we can keep track of what the approvals were before the transfer
         approved_account_ids: HashMap<AccountId, u64>,
-        //we introduce a memo for logging the transfer event
+        //only passed by callbacks scheduled before `v4`, kept so that they still decode
         memo: Option<String>,
     ) -> bool;
 }
@@ -94,16 +97,19 @@ impl NonFungibleTokenCore for Contract {
         token_id: TrailIdAndCopyNumber,
         memo: Option<String>,
     ) {
+        assert_one_yocto();
+        self.panic_if_paused(PausableOperation::Transfer);
+
         let sender_id = env::predecessor_account_id();
 
-        let (_new_token, previous_token) =
-            self.internal_transfer(&sender_id, &receiver_id, &token_id, None, memo.clone());
+        let previous_token = self.internal_transfer(&sender_id, &receiver_id, &token_id);
 
         NearEvent::log_nft_transfer(
             previous_token.owner_id.to_string(),
             receiver_id.to_string(),
             vec![token_id],
             memo,
+            //trails do not support approvals (NEP-178), so only the owner can transfer them
             None,
         );
     }
@@ -117,37 +123,39 @@ impl NonFungibleTokenCore for Contract {
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<bool> {
+        assert_one_yocto();
+        self.panic_if_paused(PausableOperation::Transfer);
+
         //get the GAS attached to the call
         let attached_gas = env::prepaid_gas();
 
         /*
             make sure that the attached gas is greater than the minimum GAS for NFT transfer call.
             This is to ensure that the cross contract call to nft_on_transfer won't cause a prepaid GAS error.
-            If this happens, the event will be logged in internal_transfer but the actual transfer logic will be
-            reverted due to the panic. This will result in the databases thinking the NFT belongs to the wrong person.
+            If this happens, the transfer would be left in place without ever being resolved.
         */
-        assert!(
+        require(
             attached_gas >= MIN_GAS_FOR_NFT_TRANSFER_CALL,
-            "You cannot attach less than {:?} Gas to nft_transfer_call",
-            MIN_GAS_FOR_NFT_TRANSFER_CALL
+            CampgroundError::NotEnoughGas(MIN_GAS_FOR_NFT_TRANSFER_CALL),
         );
 
         //get the sender ID
         let sender_id = env::predecessor_account_id();
 
         //transfer the token and get the previous token object
-        let (_new_token, previous_token) =
-            self.internal_transfer(&sender_id, &receiver_id, &token_id, None, memo.clone());
+        let previous_token = self.internal_transfer(&sender_id, &receiver_id, &token_id);
 
-        //default the authorized_id to none
-        let mut authorized_id = None;
-        //if the sender isn't the owner of the token, we set the authorized ID equal to the sender.
-        if sender_id != previous_token.owner_id {
-            authorized_id = Some(sender_id.to_string());
-        }
+        NearEvent::log_nft_transfer(
+            previous_token.owner_id.to_string(),
+            receiver_id.to_string(),
+            vec![token_id.clone()],
+            memo,
+            //trails do not support approvals (NEP-178), so only the owner can transfer them
+            None,
+        );
 
         // Initiating receiver's call and the callback
-        let call = ext_non_fungible_token_receiver::nft_on_transfer(
+        ext_non_fungible_token_receiver::nft_on_transfer(
             sender_id,
             previous_token.owner_id.clone(),
             token_id.clone(),
@@ -158,53 +166,23 @@ impl NonFungibleTokenCore for Contract {
         )
         //we then resolve the promise and call nft_resolve_transfer on our own contract
         .then(ext_self::nft_resolve_transfer(
-            authorized_id, // we introduce an authorized ID so that we can log the transfer
             previous_token.owner_id.clone(),
             receiver_id.clone(),
-            token_id.clone(),
+            token_id,
+            // Trails do not support approvals, so there are none to restore on revert
             HashMap::new(),
-            memo.clone(), // we introduce a memo for logging in the events standard
+            // Already logged along with the transfer above
+            None,
             env::current_account_id(), //contract account to make the call to
-            NO_DEPOSIT,   //attached deposit
-            GAS_FOR_RESOLVE_TRANSFER, //GAS attached to the call
+            NO_DEPOSIT,                //attached deposit
+            GAS_FOR_RESOLVE_TRANSFER,  //GAS attached to the call
         ))
-        .into();
-
-        NearEvent::log_nft_transfer(
-            previous_token.owner_id.to_string(),
-            receiver_id.to_string(),
-            vec![token_id],
-            memo,
-            None,
-        );
-
-        call
+        .into()
     }
 
     //get the information for a specific token ID
-    fn nft_token(&self, token_id: TrailIdAndCopyNumber) -> Option<JsonTrail> {
-        //if there is some token ID in the tokens_by_id collection
-        if let Some(token) = self.tokens_by_id.get(&token_id) {
-            //we'll get the metadata for that token
-            let serie = self.trails_metadata_by_id.get(&token.token_id).unwrap();
-            //we return the JsonToken (wrapped by Some since we return an option)
-
-            // We get the metadata
-            // Which we will modify adding the NFT (Trail) numeration
-            // We don't want to modify the real metadata
-            let metadata = token.partial_metadata.to_owned();
-
-            Some(format_json_trail(
-                token_id,
-                token.owner_id,
-                serie,
-                metadata,
-                true,
-            ))
-        } else {
-            //if there wasn't a token ID in the tokens_by_id collection, we return None
-            None
-        }
+    fn nft_token(&self, token_id: TrailIdAndCopyNumber) -> Option<JsonToken> {
+        self.trail_ticket(token_id).map(JsonToken::from)
     }
 }
 
@@ -212,83 +190,72 @@ impl NonFungibleTokenCore for Contract {
 impl NonFungibleTokenResolver for Contract {
     //resolves the cross contract call when calling nft_on_transfer in the nft_transfer_call method
     //returns true if the token was successfully transferred to the receiver_id
+    /// The transfer to `receiver_id` is logged by `nft_transfer_call`,
+    /// so this only logs the `nft_transfer` event back to `owner_id` when the token is returned.
+    /// Trails do not support approvals (NEP-178), so `approved_account_ids` is always empty
+    /// and no approval is restored on return.
+    /// Callbacks scheduled before `v4` also pass `memo`, logged along with the return as it was then,
+    /// and `authorized_id`, which is ignored like any other unknown argument.
     #[private]
     fn nft_resolve_transfer(
         &mut self,
-        //we introduce an authorized ID for logging the transfer event
-        _authorized_id: Option<String>,
         owner_id: AccountId,
         receiver_id: AccountId,
         token_id: TrailIdAndCopyNumber,
         //we introduce the approval map so we can keep track of what the approvals were before the transfer
-        _approved_account_ids: HashMap<AccountId, u64>,
-        //we introduce a memo for logging the transfer event
+        approved_account_ids: HashMap<AccountId, u64>,
+        //only passed by callbacks scheduled before `v4`, kept so that they still decode
         memo: Option<String>,
     ) -> bool {
-        // call result.
-        if let PromiseResult::Successful(value) = env::promise_result(0) {
-            //As per the standard, the nft_on_transfer should return whether we should return the token to it's owner or not
-            if let Ok(return_token) = near_sdk::serde_json::from_slice::<bool>(&value) {
-                //if we need don't need to return the token, we simply return true meaning everything went fine
-                if !return_token {
-                    // /*
-                    //     since we've already transferred the token and nft_on_transfer returned false, we don't have to
-                    //     revert the original transfer and thus we can just return true since nothing went wrong.
-                    // */
-                    // //we refund the owner for releasing the storage used up by the approved account IDs
-                    // refund_approved_account_ids(owner_id, &approved_account_ids);
-                    return true;
-                }
+        //As per the standard, the nft_on_transfer should return whether we should return the token to it's owner or not.
+        //If the receiver panicked, we try to return the token.
+        let must_return = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<bool>(&value).unwrap_or(true)
             }
-        }
+            _ => true,
+        };
 
-        //get the token object if there is some token object
-        let mut token = if let Some(token) = self.tokens_by_id.get(&token_id) {
-            if token.owner_id != receiver_id {
-                // //we refund the owner for releasing the storage used up by the approved account IDs
-                // refund_approved_account_ids(owner_id, &approved_account_ids);
-                // // The token is not owner by the receiver anymore. Can't return it.
+        //the token can only be returned if the receiver still owns it
+        let token = if must_return {
+            self.tokens_by_id
+                .get(&token_id)
+                .filter(|token| token.owner_id == receiver_id)
+        } else {
+            None
+        };
+
+        let mut token = match token {
+            Some(token) => token,
+            None => {
+                //the token was kept, burned or transferred again by the receiver, so the transfer is final
+                if !approved_account_ids.is_empty() {
+                    //we refund the owner for releasing the storage used up by the approved account IDs
+                    refund_approved_account_ids(owner_id, &approved_account_ids);
+                }
                 return true;
             }
-            token
-            //if there isn't a token object, it was burned and so we return true
-        } else {
-            // //we refund the owner for releasing the storage used up by the approved account IDs
-            // refund_approved_account_ids(owner_id, &approved_account_ids);
-            return true;
         };
 
         //we remove the token from the receiver
-        self.internal_remove_trail_from_owner(&receiver_id.clone(), &token_id);
+        self.internal_remove_trail_from_owner(&receiver_id, &token_id);
         //we add the token to the original owner
         self.internal_add_trail_to_owner(&owner_id, &token_id);
 
         //we change the token struct's owner to be the original owner
         token.owner_id = owner_id.clone();
 
-        // //we refund the receiver any approved account IDs that they may have set on the token
-        // refund_approved_account_ids(receiver_id.clone(), &token.approved_account_ids);
-        // //reset the approved account IDs to what they were before the transfer
-        // token.approved_account_ids = approved_account_ids;
-
         //we inset the token back into the tokens_by_id collection
         self.tokens_by_id.insert(&token_id, &token);
 
-        // TODO: ADD LOG
-
-        //we perform the actual logging
-        // env::log_str(&nft_transfer_log.to_string());
-
-        //return false
-
+        //the contract returns the token on its own, so there is no authorized account
         NearEvent::log_nft_transfer(
             receiver_id.to_string(),
-            token.owner_id.to_string(),
+            owner_id.to_string(),
             vec![token_id],
             memo,
             None,
         );
-
         false
     }
 }
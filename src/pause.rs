@@ -0,0 +1,76 @@
+use crate::event::NearEvent;
+use crate::*;
+
+/// Classes of operations that can be paused contract-wide.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PausableOperation {
+    /// Public purchases through `nft_buy_series`.
+    Buy,
+    /// Direct mints through `nft_mint`.
+    Mint,
+    /// Trail creation through `create_trail_series`.
+    Create,
+    /// Token transfers through `nft_transfer` and `nft_transfer_call`.
+    Transfer,
+    /// Price changes through `update_trail_series_price`.
+    UpdatePrice,
+}
+
+/// Provides an emergency stop for each `PausableOperation`,
+/// without having to go through every trail.
+///
+/// Only the contract `owner`, any `Admin` and any `Guardian` can pause or unpause operations,
+/// attaching exactly 1 yoctoNEAR.
+pub trait PauseBridge {
+    /// Pauses `operation` until `unpause` is called.
+    /// Returns `false` if `operation` was already paused.
+    fn pause(&mut self, operation: PausableOperation) -> bool;
+
+    /// Resumes a paused `operation`.
+    /// Returns `false` if `operation` was not paused.
+    fn unpause(&mut self, operation: PausableOperation) -> bool;
+
+    /// Whether `operation` is currently paused.
+    fn is_paused(&self, operation: PausableOperation) -> bool;
+
+    /// Returns all the operations currently paused.
+    fn get_paused_operations(&self) -> Vec<PausableOperation>;
+}
+
+#[near_bindgen]
+impl PauseBridge for Contract {
+    #[payable]
+    fn pause(&mut self, operation: PausableOperation) -> bool {
+        assert_one_yocto();
+        self.panic_if_not_guardian();
+
+        let paused = self.paused_operations.insert(&operation);
+        if paused {
+            NearEvent::log_contract_pause(operation, env::predecessor_account_id().to_string());
+        }
+        paused
+    }
+
+    #[payable]
+    fn unpause(&mut self, operation: PausableOperation) -> bool {
+        assert_one_yocto();
+        self.panic_if_not_guardian();
+
+        let unpaused = self.paused_operations.remove(&operation);
+        if unpaused {
+            NearEvent::log_contract_unpause(operation, env::predecessor_account_id().to_string());
+        }
+        unpaused
+    }
+
+    fn is_paused(&self, operation: PausableOperation) -> bool {
+        self.paused_operations.contains(&operation)
+    }
+
+    fn get_paused_operations(&self) -> Vec<PausableOperation> {
+        self.paused_operations.to_vec()
+    }
+}
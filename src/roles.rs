@@ -0,0 +1,131 @@
+use crate::event::NearEvent;
+use crate::*;
+
+/// Roles that can be granted to accounts to operate the contract.
+/// The contract `owner` is implicitly an `Admin`.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    /// Manages the configuration and grants or revokes every role but `Admin`.
+    Admin,
+    /// Toggles user minting of any trail.
+    SubAdmin,
+    /// Mints copies of any trail on behalf of fiat buyers.
+    FiatMinter,
+    /// Archives or unarchives any trail.
+    Moderator,
+    /// Changes Campground fees and treasury address.
+    FinanceManager,
+    /// Pauses or unpauses contract operations in an emergency.
+    Guardian,
+}
+
+/// Provides operations to manage the members of each `Role`.
+///
+/// Only the contract `owner` can grant or revoke the `Admin` role.
+/// The remaining roles can also be granted or revoked by any `Admin`.
+/// Granting and revoking require exactly 1 yoctoNEAR attached.
+pub trait RolesBridge {
+    /// Grants `role` to `account_id`.
+    /// Returns `false` if `account_id` already had that role.
+    fn grant_role(&mut self, role: Role, account_id: AccountId) -> bool;
+
+    /// Revokes `role` from `account_id`.
+    /// Returns `false` if `account_id` did not have that role.
+    fn revoke_role(&mut self, role: Role, account_id: AccountId) -> bool;
+
+    /// Whether `account_id` has been granted `role`.
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool;
+
+    /// Returns all the accounts that have been granted `role`.
+    fn get_role_members(&self, role: Role) -> Vec<AccountId>;
+}
+
+#[near_bindgen]
+impl RolesBridge for Contract {
+    #[payable]
+    fn grant_role(&mut self, role: Role, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        self.panic_if_cannot_manage_role(role);
+
+        let granted = self.internal_grant_role(role, &account_id);
+        if granted {
+            NearEvent::log_role_grant(
+                role,
+                account_id.to_string(),
+                env::predecessor_account_id().to_string(),
+            );
+        }
+        granted
+    }
+
+    #[payable]
+    fn revoke_role(&mut self, role: Role, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        self.panic_if_cannot_manage_role(role);
+
+        let revoked = self.internal_revoke_role(role, &account_id);
+        if revoked {
+            NearEvent::log_role_revoke(
+                role,
+                account_id.to_string(),
+                env::predecessor_account_id().to_string(),
+            );
+        }
+        revoked
+    }
+
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.role_members
+            .get(&role)
+            .is_some_and(|members| members.contains(&account_id))
+    }
+
+    fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.role_members
+            .get(&role)
+            .map_or(vec![], |members| members.to_vec())
+    }
+}
+
+impl Contract {
+    //add an account to the set of members of a role
+    pub(crate) fn internal_grant_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        let mut members = self.role_members.get(&role).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::RoleMembersInner { role }.try_to_vec().unwrap())
+        });
+
+        let granted = members.insert(account_id);
+        self.role_members.insert(&role, &members);
+        granted
+    }
+
+    //remove an account from the set of members of a role
+    pub(crate) fn internal_revoke_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        if let Some(mut members) = self.role_members.get(&role) {
+            let revoked = members.remove(account_id);
+            if members.is_empty() {
+                self.role_members.remove(&role);
+            } else {
+                self.role_members.insert(&role, &members);
+            }
+            revoked
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn is_caller_in_role(&self, role: Role) -> bool {
+        self.has_role(role, env::predecessor_account_id())
+    }
+
+    fn panic_if_cannot_manage_role(&self, role: Role) {
+        if role == Role::Admin {
+            self.panic_if_not_owner();
+        } else {
+            self.panic_if_not_admin();
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::bridge::SeriesBridge;
+use crate::internal::{get_id_and_copy, hash_account_id, hash_trail_id};
+use crate::market::get_price_and_fee;
+use crate::*;
+use near_sdk::json_types::U64;
+
+/// Bytes NEAR charges for every storage record besides its key and value.
+const STORAGE_RECORD_EXTRA_BYTES: u64 = 40;
+
+/// Storage a call would add to the contract, along with the deposit to attach.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageEstimate {
+    /// Bytes the call adds to the contract storage, `0` when it frees storage.
+    pub bytes: U64,
+    /// Cost of those bytes, in yoctoNEAR.
+    pub storage_cost: U128,
+    /// Exact deposit to attach to the call, in yoctoNEAR.
+    pub deposit: U128,
+}
+
+/// Provides views to estimate the storage of the calls that write state,
+/// computed from the serialized structures each call would write.
+///
+/// Estimates are exact for the current state, so they should be requested
+/// right before the call. For `create_trail_series`, see `create_trail_series_estimated`.
+pub trait StorageEstimateBridge {
+    /// Estimates `nft_mint` of the next copy of `token_id` for `receiver_id`.
+    /// The deposit covers the storage of the copy, as copies minted directly are paid off-chain.
+    fn nft_mint_estimated(&self, token_id: TrailId, receiver_id: AccountId) -> StorageEstimate;
+
+    /// Estimates `nft_buy_series` of the next copy of `trail_series_id` for `receiver_id`.
+    /// The deposit is the price of the trail, storage is covered by the contract.
+    fn nft_buy_series_estimated(
+        &self,
+        trail_series_id: TrailId,
+        receiver_id: AccountId,
+    ) -> StorageEstimate;
+
+    /// Estimates `nft_transfer` of `token_id` to `receiver_id`.
+    /// The deposit is the 1 yoctoNEAR required, storage is covered by the contract.
+    fn nft_transfer_estimated(
+        &self,
+        receiver_id: AccountId,
+        token_id: TrailIdAndCopyNumber,
+    ) -> StorageEstimate;
+}
+
+#[near_bindgen]
+impl StorageEstimateBridge for Contract {
+    fn nft_mint_estimated(&self, token_id: TrailId, receiver_id: AccountId) -> StorageEstimate {
+        let bytes = self.internal_mint_storage(&token_id, &receiver_id);
+        let storage_cost = Balance::from(bytes) * env::storage_byte_cost();
+        StorageEstimate {
+            bytes: bytes.into(),
+            storage_cost: storage_cost.into(),
+            deposit: storage_cost.into(),
+        }
+    }
+
+    fn nft_buy_series_estimated(
+        &self,
+        trail_series_id: TrailId,
+        receiver_id: AccountId,
+    ) -> StorageEstimate {
+        let bytes = self.internal_mint_storage(&trail_series_id, &receiver_id);
+        let (price, _) = get_price_and_fee(&self.get_trail_by_id(&trail_series_id));
+        StorageEstimate {
+            bytes: bytes.into(),
+            storage_cost: (Balance::from(bytes) * env::storage_byte_cost()).into(),
+            deposit: price.into(),
+        }
+    }
+
+    fn nft_transfer_estimated(
+        &self,
+        receiver_id: AccountId,
+        token_id: TrailIdAndCopyNumber,
+    ) -> StorageEstimate {
+        let token = self
+            .tokens_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| CampgroundError::TrailCopyNotFound.panic());
+        require(receiver_id != token.owner_id, CampgroundError::SelfTransfer);
+
+        let mint_index = self.internal_mint_index(&token_id);
+        let added = self.internal_owner_storage(&receiver_id, &token_id, mint_index, false)
+            + receiver_id.as_str().len() as u64;
+        let freed = self.internal_owner_storage(&token.owner_id, &token_id, mint_index, true)
+            + token.owner_id.as_str().len() as u64;
+
+        let bytes = added.saturating_sub(freed);
+        StorageEstimate {
+            bytes: bytes.into(),
+            storage_cost: (Balance::from(bytes) * env::storage_byte_cost()).into(),
+            deposit: 1.into(),
+        }
+    }
+}
+
+impl Contract {
+    /// Bytes added by `create_trail_series` storing `trail_series` under `trail_id`.
+    pub(crate) fn internal_create_storage(
+        &self,
+        trail_id: &TrailId,
+        trail_series: &TrailSeries,
+        is_mintable: bool,
+    ) -> u64 {
+        let creator_id = &trail_series.creator_id;
+        let mut bytes = map_entry_bytes(&StorageKey::TrailsMetadataById, trail_id, trail_series);
+
+        let creator_prefix = StorageKey::TokenPerCreatorInner {
+            account_id_hash: hash_account_id(creator_id),
+        };
+        if self.trails_series_by_creator.get(creator_id).is_none() {
+            let trails = UnorderedSet::<TrailId>::new(creator_prefix.try_to_vec().unwrap());
+            bytes += lookup_entry_bytes(&StorageKey::TokenPerCreator, creator_id, &trails);
+        }
+        bytes += set_entry_bytes(&creator_prefix, trail_id);
+
+        if !is_mintable {
+            bytes += lookup_entry_bytes(&StorageKeysV3::NonMintableTrails, trail_id, &());
+        }
+        bytes
+    }
+
+    /// Bytes added by minting the next copy of `series_id` for `receiver_id`.
+    fn internal_mint_storage(&self, series_id: &TrailId, receiver_id: &AccountId) -> u64 {
+        let series = self.get_trail_by_id(series_id);
+        let copy_number = series.supply.circulating + 1;
+        let token_id: TrailIdAndCopyNumber =
+            format!("{}{}{}", series_id, TRAIL_DELIMETER, copy_number);
+        let token = TrailBusiness {
+            owner_id: receiver_id.clone(),
+            token_id: series_id.clone(),
+            copy_number,
+            issued_at: Some(0),
+        };
+        let mint_index = self.token_metadata_by_id.len();
+
+        let mut bytes = lookup_entry_bytes(&StorageKey::TokensById, &token_id, &token)
+            + map_entry_bytes(&StorageKey::TokenMetadataById, &token_id, series_id)
+            + lookup_entry_bytes(&StorageKey::TokenMintIndex, &token_id, &mint_index)
+            + self.internal_owner_storage(receiver_id, &token_id, mint_index, false);
+
+        let series_prefix = StorageKey::TokensPerTypeInner {
+            token_type_hash: hash_trail_id(series_id),
+        };
+        if self.tokens_per_series.get(series_id).is_none() {
+            let tokens =
+                UnorderedSet::<TrailIdAndCopyNumber>::new(series_prefix.try_to_vec().unwrap());
+            bytes += lookup_entry_bytes(&StorageKey::TokensPerType, series_id, &tokens);
+        }
+        bytes + set_entry_bytes(&series_prefix, &token_id)
+    }
+
+    /// Bytes of the per-owner indexes of `token_id` held by `account_id`,
+    /// added when it is received or freed when it is given away (`is_removal`).
+    /// The holders map of the series itself is not counted on removal,
+    /// as a copy always leaves it for another account.
+    fn internal_owner_storage(
+        &self,
+        account_id: &AccountId,
+        token_id: &TrailIdAndCopyNumber,
+        mint_index: u64,
+        is_removal: bool,
+    ) -> u64 {
+        let account_id_hash = hash_account_id(account_id);
+        let mut bytes = 0;
+
+        let tokens_prefix = StorageKey::TokenPerOwnerInner { account_id_hash };
+        let tokens = self.tokens_per_owner.get(account_id);
+        let tokens_len = tokens.as_ref().map_or(0, |tokens| tokens.len());
+        if tokens_len == u64::from(is_removal) {
+            let tokens =
+                tokens.unwrap_or_else(|| UnorderedSet::new(tokens_prefix.try_to_vec().unwrap()));
+            bytes += lookup_entry_bytes(&StorageKey::TokensPerOwner, account_id, &tokens);
+        }
+        bytes += set_entry_bytes(&tokens_prefix, token_id);
+
+        let by_mint_prefix = StorageKey::TokensPerOwnerByMintInner { account_id_hash };
+        let by_mint = self.tokens_per_owner_by_mint.get(account_id);
+        let by_mint_len = by_mint.as_ref().map_or(0, |tokens| tokens.len());
+        if by_mint_len == u64::from(is_removal) {
+            let by_mint =
+                by_mint.unwrap_or_else(|| TreeMap::new(by_mint_prefix.try_to_vec().unwrap()));
+            bytes += lookup_entry_bytes(&StorageKey::TokensPerOwnerByMint, account_id, &by_mint);
+        }
+        // Every node but the root takes a link from its parent
+        let is_only_node = by_mint_len == u64::from(is_removal);
+        bytes += tree_entry_bytes(&by_mint_prefix, &mint_index, token_id, is_only_node);
+
+        let series_id = get_id_and_copy(token_id.clone()).0;
+        let holders_prefix = StorageKey::HoldersPerSeriesInner {
+            trail_id_hash: hash_trail_id(&series_id),
+        };
+        let holders = self.holders_per_series.get(&series_id);
+        let copies = holders
+            .as_ref()
+            .and_then(|holders| holders.get(account_id))
+            .unwrap_or(0);
+        if copies == u64::from(is_removal) {
+            bytes += map_entry_bytes(&holders_prefix, account_id, &copies);
+        }
+        if holders.is_none() {
+            let holders = UnorderedMap::<AccountId, u64>::new(holders_prefix.try_to_vec().unwrap());
+            bytes += lookup_entry_bytes(&StorageKey::HoldersPerSeries, &series_id, &holders);
+        }
+
+        let series_prefix = StorageKey::SeriesPerOwnerInner { account_id_hash };
+        let series = self.series_per_owner.get(account_id);
+        let series_len = series.as_ref().map_or(0, |series| series.len());
+        if copies == u64::from(is_removal) {
+            bytes += map_entry_bytes(&series_prefix, &series_id, &copies);
+            if series_len == u64::from(is_removal) {
+                let series = series
+                    .unwrap_or_else(|| UnorderedMap::new(series_prefix.try_to_vec().unwrap()));
+                bytes += lookup_entry_bytes(&StorageKey::SeriesPerOwner, account_id, &series);
+            }
+        }
+        bytes
+    }
+}
+
+fn serialized_len<T: BorshSerialize>(value: &T) -> u64 {
+    value.try_to_vec().unwrap().len() as u64
+}
+
+/// Bytes of a record stored under `prefix` followed by `key`.
+fn record_bytes<P: BorshSerialize>(prefix: &P, key_len: u64, value_len: u64) -> u64 {
+    serialized_len(prefix) + key_len + value_len + STORAGE_RECORD_EXTRA_BYTES
+}
+
+/// Bytes of an entry of a `LookupMap` or `LookupSet` with `prefix`.
+fn lookup_entry_bytes<P, K, V>(prefix: &P, key: &K, value: &V) -> u64
+where
+    P: BorshSerialize,
+    K: BorshSerialize,
+    V: BorshSerialize,
+{
+    record_bytes(prefix, serialized_len(key), serialized_len(value))
+}
+
+/// Bytes of an element of an `UnorderedSet` with `prefix`:
+/// its index and its slot in the elements vector.
+fn set_entry_bytes<P: BorshSerialize, T: BorshSerialize>(prefix: &P, element: &T) -> u64 {
+    let element_len = serialized_len(element);
+    record_bytes(prefix, 1 + element_len, 8) + record_bytes(prefix, 1 + 8, element_len)
+}
+
+/// Bytes of an entry of an `UnorderedMap` with `prefix`:
+/// its index and its slots in the keys and values vectors.
+fn map_entry_bytes<P, K, V>(prefix: &P, key: &K, value: &V) -> u64
+where
+    P: BorshSerialize,
+    K: BorshSerialize,
+    V: BorshSerialize,
+{
+    let key_len = serialized_len(key);
+    record_bytes(prefix, 1 + key_len, 8)
+        + record_bytes(prefix, 1 + 8, key_len)
+        + record_bytes(prefix, 1 + 8, serialized_len(value))
+}
+
+/// Bytes of an entry of a `TreeMap` with `prefix`: its value and its node,
+/// which holds the key, its id, height and links to its children.
+fn tree_entry_bytes<P, K, V>(prefix: &P, key: &K, value: &V, is_only_node: bool) -> u64
+where
+    P: BorshSerialize,
+    K: BorshSerialize,
+    V: BorshSerialize,
+{
+    let key_len = serialized_len(key);
+    let node_len = 8 + key_len + 1 + 1 + 8 + if is_only_node { 0 } else { 8 };
+    record_bytes(prefix, 1 + key_len, serialized_len(value)) + record_bytes(prefix, 1 + 8, node_len)
+}
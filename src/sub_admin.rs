@@ -1,8 +1,10 @@
-use crate::*;
 use crate::bridge::SeriesBridge;
-use crate::vars::SUB_ADMIN_ADDRESS;
+use crate::event::NearEvent;
+use crate::roles::Role;
+use crate::*;
 
-/// This trait complies with functions that can only be accessed by settings.SUB_ADMIN_ADDRESS
+/// This trait complies with functions that can only be accessed by accounts with the `SubAdmin` role
+/// Changes require exactly 1 yoctoNEAR attached
 pub trait SubAdminBridge {
     /// Remove a trail from nonmintable_trails so that it can be minted by users again
     fn remove_trail_from_nonmintable_list(&mut self, trail_id: TrailId) -> bool;
@@ -10,9 +12,6 @@ pub trait SubAdminBridge {
     /// Verifies whether caller is subadmin
     fn is_caller_subadmin(&self) -> bool;
 
-    /// Gets accountId of sub admin
-    fn get_subadmin(&self) -> AccountId;
-
     /// Includes a trail in the list of non-user mintable
     fn insert_trail_from_nonmintable_list(&mut self, trail_id: TrailId) -> bool;
 
@@ -22,32 +21,57 @@ pub trait SubAdminBridge {
     /// Toggle minting for all trails
     /// enable_minting must be true to make all trails accept public minting (nft_buy_series)
     /// Or false to lock them all
+    /// For a contract-wide emergency stop, use `pause` instead.
     fn toggle_for_all(&mut self, enable_minting: bool);
 }
 
 #[near_bindgen]
 impl SubAdminBridge for Contract {
+    #[payable]
     fn remove_trail_from_nonmintable_list(&mut self, trail_id: TrailId) -> bool {
+        assert_one_yocto();
         self.verify_toggle_minting_caller(&trail_id);
-        self.nonmintable_trails.remove(&trail_id)
+        let toggled = self.nonmintable_trails.remove(&trail_id);
+        if toggled {
+            NearEvent::log_series_mintability_update(
+                trail_id,
+                true,
+                env::predecessor_account_id().to_string(),
+            );
+        }
+        toggled
     }
 
+    #[payable]
     fn insert_trail_from_nonmintable_list(&mut self, trail_id: TrailId) -> bool {
+        assert_one_yocto();
         self.verify_toggle_minting_caller(&trail_id);
-        self.nonmintable_trails.insert(&trail_id)
+        let toggled = self.nonmintable_trails.insert(&trail_id);
+        if toggled {
+            NearEvent::log_series_mintability_update(
+                trail_id,
+                false,
+                env::predecessor_account_id().to_string(),
+            );
+        }
+        toggled
     }
 
     fn verify_toggle_minting_caller(&self, trail_id: &TrailId) {
-        let trail = self.get_trail_by_id_optional(trail_id).expect("Trail does not exist");
+        let trail = self
+            .get_trail_by_id_optional(trail_id)
+            .unwrap_or_else(|| CampgroundError::TrailNotFound.panic());
         let is_sender_owner = trail.creator_id == env::predecessor_account_id();
         if !is_sender_owner {
             self.panic_if_not_subadmin();
         }
     }
 
+    #[payable]
     fn toggle_for_all(&mut self, enable_minting: bool) {
+        assert_one_yocto();
         self.panic_if_not_subadmin();
-        let keys: Vec<TrailId> = self.trails_metadata_by_id.keys().map(|trail_id| trail_id).collect();
+        let keys: Vec<TrailId> = self.trails_metadata_by_id.keys().collect();
         for trail_id in keys {
             if enable_minting {
                 self.remove_trail_from_nonmintable_list(trail_id);
@@ -58,15 +82,6 @@ impl SubAdminBridge for Contract {
     }
 
     fn is_caller_subadmin(&self) -> bool {
-        let subadmin = self.get_subadmin();
-        let predecessor = env::predecessor_account_id();
-        subadmin == predecessor
-    }
-
-    fn get_subadmin(&self) -> AccountId {
-        let key = String::from(SUB_ADMIN_ADDRESS);
-        let value = self.settings.get(&key).unwrap_or_else(|| String::new());
-        let acct_id: AccountId = AccountId::try_from(value).unwrap();
-        acct_id
+        self.is_caller_in_role(Role::SubAdmin)
     }
-}
\ No newline at end of file
+}
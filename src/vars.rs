@@ -1,2 +1,3 @@
+/// Keys of the `settings` map used up to `v3`, migrated to the `FiatMinter` and `SubAdmin` roles.
 pub const WHITELISTED_ADDRESS_MINTING_KEY: &str = "MINTING_WHITELISTED_ADDRESS";
-pub const SUB_ADMIN_ADDRESS: &str = "SUB_ADMIN_ADDRESS";
\ No newline at end of file
+pub const SUB_ADMIN_ADDRESS: &str = "SUB_ADMIN_ADDRESS";
@@ -1,11 +1,16 @@
 pub mod context;
 
-use ito_contract::admin::AdminBridge;
+use ito_contract::admin::{AdminBridge, AdminChange};
+use ito_contract::bridge::SeriesBridge;
+use ito_contract::{DEFAULT_ADMIN_CHANGE_DELAY, MAX_CAMPGROUND_MINIMUM_FEE};
 use near_sdk::testing_env;
 
-use context::{alice, new_treasury, owner, setup_contract, STORAGE_FOR_CREATE_SERIES};
+use context::{
+    alice, bob, create_series, execute_pending_admin_changes, new_treasury, owner, setup_contract,
+    STORAGE_FOR_CREATE_SERIES,
+};
+use ito_contract::roles::{Role, RolesBridge};
 use ito_contract::sub_admin::SubAdminBridge;
-use ito_contract::vars::SUB_ADMIN_ADDRESS;
 
 #[test]
 fn contract_should_change_campground_fee() {
@@ -13,21 +18,24 @@ fn contract_should_change_campground_fee() {
     assert_eq!(contract.campground_fee, 5);
     testing_env!(context
         .predecessor_account_id(owner())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.change_campground_fee(10);
+    assert_ne!(contract.campground_fee, 10);
+
+    execute_pending_admin_changes(&mut context, &mut contract);
     assert_eq!(contract.campground_fee, 10);
 }
 
 #[test]
-#[should_panic(expected = "Campground: Only contract owner can execute")]
+#[should_panic(expected = "Campground: Only contract owner, Admin or FinanceManager can execute")]
 fn contract_should_reject_non_owner_changing_campground_fee() {
     let (mut context, mut contract) = setup_contract();
     assert_eq!(contract.campground_fee, 5);
     testing_env!(context
         .predecessor_account_id(alice())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.change_campground_fee(10);
@@ -39,21 +47,24 @@ fn contract_should_change_campground_treasury_address() {
     assert_eq!(contract.campground_fee, 5);
     testing_env!(context
         .predecessor_account_id(owner())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.change_campground_treasury_address(new_treasury());
+    assert_ne!(contract.campground_treasury_address, new_treasury());
+
+    execute_pending_admin_changes(&mut context, &mut contract);
     assert_eq!(contract.campground_treasury_address, new_treasury());
 }
 
 #[test]
-#[should_panic(expected = "Campground: Only contract owner can execute")]
+#[should_panic(expected = "Campground: Only contract owner, Admin or FinanceManager can execute")]
 fn contract_should_reject_when_non_owner_changing_campground_treasury_address() {
     let (mut context, mut contract) = setup_contract();
     assert_eq!(contract.campground_fee, 5);
     testing_env!(context
         .predecessor_account_id(alice())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.change_campground_treasury_address(new_treasury());
@@ -65,21 +76,24 @@ fn contract_should_change_campground_minimum_fee() {
     assert_eq!(contract.campground_fee, 5);
     testing_env!(context
         .predecessor_account_id(owner())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.change_campground_minimum_fee(50000000);
+    assert_ne!(contract.campground_minimum_fee_yocto_near, 50000000);
+
+    execute_pending_admin_changes(&mut context, &mut contract);
     assert_eq!(contract.campground_minimum_fee_yocto_near, 50000000);
 }
 
 #[test]
-#[should_panic(expected = "Campground: Only contract owner can execute")]
+#[should_panic(expected = "Campground: Only contract owner, Admin or FinanceManager can execute")]
 fn contract_should_reject_when_non_owner_changing_campground_minimum_fee() {
     let (mut context, mut contract) = setup_contract();
     assert_eq!(contract.campground_fee, 5);
     testing_env!(context
         .predecessor_account_id(alice())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.change_campground_minimum_fee(50000000);
@@ -93,16 +107,31 @@ fn contract_should_reject_when_non_owner_changing_campground_minimum_fee() {
 fn sub_admin_should_be_able_to_remove_trail() {
     let (mut context, mut contract) = setup_contract();
     testing_env!(context
-        .predecessor_account_id(owner())
+        .predecessor_account_id(bob())
         .attached_deposit(STORAGE_FOR_CREATE_SERIES)
         .build());
+    create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(false),
+    );
 
-    contract.add_setting(String::from(SUB_ADMIN_ADDRESS), String::from("alice"));
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.grant_role(Role::SubAdmin, alice());
     contract.nonmintable_trails.insert(&String::from("1"));
 
     testing_env!(context
         .predecessor_account_id(alice())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
 
     contract.remove_trail_from_nonmintable_list(String::from("1"));
@@ -113,11 +142,319 @@ fn sub_admin_should_be_able_to_remove_trail() {
 fn any_user_cantremove_from_non_mintable_trail() {
     let (mut context, mut contract) = setup_contract();
     testing_env!(context
-        .predecessor_account_id(owner())
+        .predecessor_account_id(bob())
         .attached_deposit(STORAGE_FOR_CREATE_SERIES)
         .build());
+    create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(false),
+    );
 
-    contract.add_setting(String::from(SUB_ADMIN_ADDRESS), String::from("alice"));
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.grant_role(Role::SubAdmin, alice());
     contract.nonmintable_trails.insert(&String::from("1"));
     contract.remove_trail_from_nonmintable_list(String::from("1"));
-}
\ No newline at end of file
+}
+
+///
+/// Ownership transfer tests
+///
+
+#[test]
+fn owner_should_transfer_ownership_once_accepted() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.propose_owner(alice());
+    assert_eq!(contract.get_pending_owner(), Some(alice()));
+    assert_eq!(contract.get_owner(), &owner());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.accept_ownership();
+    assert_eq!(contract.get_owner(), &alice());
+    assert_eq!(contract.get_pending_owner(), None);
+
+    contract.change_campground_fee(10);
+    execute_pending_admin_changes(&mut context, &mut contract);
+    assert_eq!(contract.campground_fee, 10);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner can execute")]
+fn non_owner_should_not_propose_owner() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+
+    contract.propose_owner(alice());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only the proposed owner can accept the ownership")]
+fn only_proposed_owner_should_accept_ownership() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.propose_owner(alice());
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.accept_ownership();
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only the proposed owner can accept the ownership")]
+fn cancelled_proposal_should_not_be_accepted() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.propose_owner(alice());
+    contract.cancel_ownership_proposal();
+    assert_eq!(contract.get_pending_owner(), None);
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.accept_ownership();
+}
+
+#[test]
+#[should_panic(expected = "Campground: There is no pending ownership transfer")]
+fn owner_should_not_cancel_missing_proposal() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.cancel_ownership_proposal();
+}
+
+///
+/// Timelocked admin changes tests
+///
+
+#[test]
+#[should_panic(expected = "Campground: Admin change is still timelocked")]
+fn admin_change_should_not_be_executed_before_delay() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    let change_id = contract.change_campground_fee(10);
+    contract.execute_admin_change(change_id);
+}
+
+#[test]
+fn owner_should_cancel_admin_change() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    let fee_change_id = contract.change_campground_fee(10);
+    contract.change_campground_treasury_address(new_treasury());
+
+    let pending = contract.get_pending_admin_changes();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].change, AdminChange::Fee(10));
+    assert_eq!(pending[0].executable_at, contract.get_admin_change_delay());
+
+    contract.cancel_admin_change(fee_change_id);
+    assert_eq!(contract.get_pending_admin_changes().len(), 1);
+
+    execute_pending_admin_changes(&mut context, &mut contract);
+    assert_eq!(contract.campground_fee, 5);
+    assert_eq!(contract.campground_treasury_address, new_treasury());
+    assert!(contract.get_pending_admin_changes().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner can execute")]
+fn finance_manager_should_not_cancel_admin_change() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FinanceManager, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    let change_id = contract.change_campground_fee(10);
+    contract.cancel_admin_change(change_id);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Fee must be between 0 and 100")]
+fn contract_should_reject_campground_fee_greater_than_100() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.change_campground_fee(101);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Minimum fee cannot exceed MAX_CAMPGROUND_MINIMUM_FEE")]
+fn contract_should_reject_minimum_fee_above_ceiling() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.change_campground_minimum_fee(MAX_CAMPGROUND_MINIMUM_FEE + 1);
+}
+
+#[test]
+fn admin_change_delay_should_be_timelocked() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    assert_eq!(
+        contract.get_admin_change_delay(),
+        DEFAULT_ADMIN_CHANGE_DELAY
+    );
+
+    contract.change_admin_change_delay(0);
+    assert_eq!(
+        contract.get_admin_change_delay(),
+        DEFAULT_ADMIN_CHANGE_DELAY
+    );
+
+    execute_pending_admin_changes(&mut context, &mut contract);
+    assert_eq!(contract.get_admin_change_delay(), 0);
+
+    let change_id = contract.change_campground_fee(10);
+    contract.execute_admin_change(change_id);
+    assert_eq!(contract.campground_fee, 10);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner or Admin can execute")]
+fn finance_manager_should_not_change_admin_change_delay() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FinanceManager, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.change_admin_change_delay(0);
+}
+
+///
+/// One yocto tests
+///
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_fee_change_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(0)
+        .build());
+    contract.change_campground_fee(10);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_admin_change_execution_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    let change_id = contract.change_campground_fee(10);
+
+    testing_env!(context
+        .block_timestamp(DEFAULT_ADMIN_CHANGE_DELAY)
+        .attached_deposit(0)
+        .build());
+    contract.execute_admin_change(change_id);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_ownership_proposal_with_more_than_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(2)
+        .build());
+    contract.propose_owner(alice());
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_ownership_acceptance_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.propose_owner(alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(0)
+        .build());
+    contract.accept_ownership();
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn sub_admin_should_not_toggle_minting_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::SubAdmin, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(0)
+        .build());
+    contract.toggle_for_all(false);
+}
@@ -0,0 +1,252 @@
+pub mod context;
+
+use ito_contract::{
+    bridge::SeriesBridge,
+    config::{ConfigBridge, ConfigEntry, ConfigKey},
+    roles::{Role, RolesBridge},
+    DEFAULT_FALLBACK_MEDIA, MAX_PRICE, ONE_NEAR,
+};
+use near_sdk::testing_env;
+
+use context::{alice, bob, create_series, owner, setup_contract, STORAGE_FOR_CREATE_SERIES};
+
+#[test]
+fn config_should_default_when_not_set() {
+    let (_, contract) = setup_contract();
+
+    assert_eq!(
+        contract.get_config(ConfigKey::MaxPrice),
+        ConfigEntry::MaxPrice(MAX_PRICE.into())
+    );
+    assert_eq!(
+        contract.get_configs(),
+        vec![
+            ConfigEntry::MaxPrice(MAX_PRICE.into()),
+            ConfigEntry::FallbackMedia(Some(DEFAULT_FALLBACK_MEDIA.to_string())),
+            ConfigEntry::MaxTitleLength(256),
+            ConfigEntry::MaxDescriptionLength(4096),
+            ConfigEntry::MaxExtraLength(8192),
+            ConfigEntry::MaxUriLength(2048),
+            ConfigEntry::MaxResources(32),
+        ]
+    );
+}
+
+#[test]
+fn admin_should_set_and_remove_config() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Admin, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.set_config(ConfigEntry::MaxPrice(ONE_NEAR.into()));
+    assert_eq!(
+        contract.get_config(ConfigKey::MaxPrice),
+        ConfigEntry::MaxPrice(ONE_NEAR.into())
+    );
+
+    assert!(contract.remove_config(ConfigKey::MaxPrice));
+    assert!(!contract.remove_config(ConfigKey::MaxPrice));
+    assert_eq!(
+        contract.get_config(ConfigKey::MaxPrice),
+        ConfigEntry::MaxPrice(MAX_PRICE.into())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: MaxPrice must be between 1 and")]
+fn contract_should_reject_invalid_config_value() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.set_config(ConfigEntry::MaxPrice((MAX_PRICE + 1).into()));
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner or Admin can execute")]
+fn regular_account_should_not_remove_config() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+
+    contract.remove_config(ConfigKey::MaxPrice);
+}
+
+#[test]
+#[should_panic(expected = "Campground: price higher than 1000000000000000000000000")]
+fn contract_should_reject_trail_priced_above_configured_max_price() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.set_config(ConfigEntry::MaxPrice(ONE_NEAR.into()));
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        None,
+        None,
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn admin_should_not_set_config_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(0)
+        .build());
+    contract.set_config(ConfigEntry::MaxPrice(ONE_NEAR.into()));
+}
+
+#[test]
+fn admin_should_configure_fallback_media() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.set_config(ConfigEntry::FallbackMedia(Some(
+        "https://example.com/fallback.png".to_string(),
+    )));
+    assert_eq!(
+        contract.get_config(ConfigKey::FallbackMedia),
+        ConfigEntry::FallbackMedia(Some("https://example.com/fallback.png".to_string()))
+    );
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        trail.metadata.media,
+        Some("https://example.com/fallback.png".to_string())
+    );
+    contract.nft_mint(trail.token_id, bob());
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.set_config(ConfigEntry::FallbackMedia(None));
+    let ticket = contract.trail_ticket("1:1".to_string()).unwrap();
+    assert_eq!(ticket.metadata.media, None);
+
+    assert!(contract.remove_config(ConfigKey::FallbackMedia));
+    assert_eq!(
+        contract.get_config(ConfigKey::FallbackMedia),
+        ConfigEntry::FallbackMedia(Some(DEFAULT_FALLBACK_MEDIA.to_string()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: FallbackMedia cannot be empty")]
+fn contract_should_reject_empty_fallback_media() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.set_config(ConfigEntry::FallbackMedia(Some(String::new())));
+}
+
+#[test]
+#[should_panic(
+    expected = "Campground: FallbackMedia must be a relative path or use one of the schemes"
+)]
+fn contract_should_reject_fallback_media_with_unsupported_scheme() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.set_config(ConfigEntry::FallbackMedia(Some(
+        "javascript:alert(1)".to_string(),
+    )));
+}
+
+#[test]
+#[should_panic(expected = "Campground: FallbackMedia cannot be longer than 16 bytes (E222)")]
+fn contract_should_reject_fallback_media_longer_than_uri_limit() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.set_config(ConfigEntry::MaxUriLength(16));
+    contract.set_config(ConfigEntry::FallbackMedia(Some(
+        "https://example.com/fallback.png".to_string(),
+    )));
+}
+
+#[test]
+#[should_panic(expected = "Campground: MaxTitleLength must be between 1 and 65536 (E511)")]
+fn contract_should_reject_zero_metadata_limit() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    contract.set_config(ConfigEntry::MaxTitleLength(0));
+}
+
+#[test]
+#[should_panic(expected = "Campground: title cannot be longer than 4 bytes (E222)")]
+fn contract_should_apply_configured_metadata_limits() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.set_config(ConfigEntry::MaxTitleLength(4));
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+}
@@ -1,36 +1,46 @@
 use ito_contract::admin::AdminBridge;
-use ito_contract::vars::WHITELISTED_ADDRESS_MINTING_KEY;
+use ito_contract::roles::{Role, RolesBridge};
 use ito_contract::{
     create_serie::CreateTrailSeries, Contract, JsonTrail, TrailResource, TrailSeriesMetadata,
 };
-use near_sdk::{json_types::U128, test_utils::VMContextBuilder, testing_env, AccountId, Balance};
+use near_sdk::{
+    env, json_types::U128, test_utils::VMContextBuilder, testing_env, AccountId, Balance,
+};
 
-pub const STORAGE_FOR_CREATE_SERIES: Balance = 6960000000000000000000;
+/// Deposit large enough to cover the storage of a default series, or of its first copy.
+pub const STORAGE_FOR_CREATE_SERIES: Balance = 30000000000000000000000;
 
+/// Owner of the contract.
 pub fn owner() -> AccountId {
     AccountId::new_unchecked("campground_owner.near".into())
 }
 
+/// Regular account, usually acting as a trail creator.
 pub fn alice() -> AccountId {
     AccountId::new_unchecked("alice".into())
 }
 
+/// Regular account, usually acting as a buyer.
 pub fn bob() -> AccountId {
     AccountId::new_unchecked("bob".into())
 }
 
+/// Regular account, granted the `FiatMinter` role by `setup_contract`.
 pub fn carol() -> AccountId {
     AccountId::new_unchecked("carol".into())
 }
 
+/// Treasury the contract is initialized with.
 pub fn treasury() -> AccountId {
     AccountId::new_unchecked("campground_treasury.near".into())
 }
 
+/// Treasury used when testing address changes.
 pub fn new_treasury() -> AccountId {
     AccountId::new_unchecked("campground_new_treasury.near".into())
 }
 
+/// Builds a context where `predecessor_account_id` is both signer and predecessor.
 pub fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
     let mut builder = VMContextBuilder::new();
     builder
@@ -40,17 +50,20 @@ pub fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
     builder
 }
 
+/// Initializes the contract with default metadata and `carol` as `FiatMinter`.
 pub fn setup_contract() -> (VMContextBuilder, Contract) {
     let mut context = VMContextBuilder::new();
-    testing_env!(context.predecessor_account_id(owner()).build());
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
     let mut contract = Contract::new_default_meta(owner(), treasury());
-    contract.add_setting(
-        String::from(WHITELISTED_ADDRESS_MINTING_KEY),
-        carol().to_string(),
-    );
+    contract.grant_role(Role::FiatMinter, carol());
     (context, contract)
 }
 
+/// Creates a trail series with a single resource, filling defaults for missing values.
+#[allow(clippy::too_many_arguments)]
 pub fn create_series(
     contract: &mut Contract,
     title: &str,
@@ -79,10 +92,25 @@ pub fn create_series(
             expires_at,
             reference: None,
             campground_id: String::from("CMPGR123"),
+            media_hash: None,
+            fallback_media: None,
         },
         price,
         None,
         None,
         allow_user_minting,
+        None,
     )
 }
+
+/// Waits for the admin change delay and executes every pending admin change
+/// as the current predecessor.
+pub fn execute_pending_admin_changes(context: &mut VMContextBuilder, contract: &mut Contract) {
+    testing_env!(context
+        .block_timestamp(env::block_timestamp() + contract.get_admin_change_delay())
+        .attached_deposit(1)
+        .build());
+    for pending in contract.get_pending_admin_changes() {
+        contract.execute_admin_change(pending.change_id);
+    }
+}
@@ -2,12 +2,16 @@ pub mod context;
 
 use ito_contract::{
     bridge::SeriesBridge, create_serie::CreateTrailSeries,
-    enumeration::NonFungibleTokenEnumeration, Contract, TrailResource, TrailSeriesMetadata,
-    ONE_NEAR,
+    enumeration::NonFungibleTokenEnumeration, nft_core::NonFungibleTokenCore, Contract, JsonTrail,
+    JsonTrailSeries, NFTContractMetadata, TrailResource, TrailSeriesMetadata, ONE_NEAR,
 };
-use near_sdk::{env, json_types::U128, testing_env};
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::{env, serde_json, test_utils::get_logs, testing_env};
 
-use context::{alice, bob, carol, create_series, setup_contract, STORAGE_FOR_CREATE_SERIES};
+use context::{
+    alice, bob, carol, create_series, get_context, owner, setup_contract, treasury,
+    STORAGE_FOR_CREATE_SERIES,
+};
 
 #[test]
 fn contract_should_allow_account_to_create_trail_series() {
@@ -23,7 +27,7 @@ fn contract_should_allow_account_to_create_trail_series() {
         "CampgroundTest",
         Some(1647109675),
         Some(1647216000),
-        Some((1 * 10u128.pow(24)).into()),
+        Some(10u128.pow(24).into()),
         None,
         None,
         None,
@@ -41,7 +45,7 @@ fn contract_should_allow_account_to_create_trail_series() {
         trail_by_id.metadata.starts_at
     );
     assert_eq!(
-        trail_by_id.metadata.resources.get(0).unwrap().media,
+        trail_by_id.metadata.resources.first().unwrap().media,
         "CampgroundTest.png"
     );
 }
@@ -81,8 +85,8 @@ fn contract_should_reject_creating_trail_series_with_invalid_ticket_amount() {
         "CampgroundTest",
         Some(1647109675),
         Some(1647216000),
-        Some(U128::from(1 * 10u128.pow(24))),
-        Some(0 as u64),
+        Some(U128::from(10u128.pow(24))),
+        Some(0),
         None,
         None,
     );
@@ -102,8 +106,8 @@ fn contract_should_reject_creating_trail_series_with_invalid_resources_amount()
         "CampgroundTest",
         Some(1647109675),
         Some(1647216000),
-        Some(U128::from(1 * 10u128.pow(24))),
-        Some(1 as u64),
+        Some(U128::from(10u128.pow(24))),
+        Some(1),
         Some(vec![]),
         None,
     );
@@ -123,25 +127,25 @@ fn contract_should_reject_minting_when_tickets_are_sold_out() {
         "CampgroundTest",
         Some(1647109675),
         Some(1647216000),
-        Some(U128::from(1 * 10u128.pow(24))),
+        Some(U128::from(10u128.pow(24))),
         Some(1),
         None,
         None,
     );
     let trail_by_id = contract.get_trail_by_id(&"1".into());
-    assert_eq!(trail_by_id.is_mintable, true);
+    assert!(trail_by_id.is_mintable);
     contract.nft_mint("1".into(), bob());
 
     let track_by_owner = contract.trail_tickets_for_owner(bob(), None, None);
     assert_eq!(track_by_owner.len(), 1);
-    println!("{}", track_by_owner.get(0).unwrap().series.is_mintable);
+    println!("{}", track_by_owner.first().unwrap().series.is_mintable);
 
     // Panics
     contract.nft_mint("1".into(), bob());
 }
 
 #[test]
-#[should_panic(expected = "Campground: Only Trail creator can directly mint")]
+#[should_panic(expected = "Campground: Only FiatMinter can execute")]
 fn only_trail_creator_can_directly_mint_test() {
     let (mut context, mut contract) = setup_contract();
     testing_env!(context
@@ -154,12 +158,12 @@ fn only_trail_creator_can_directly_mint_test() {
         "CampgroundTest",
         Some(1647109675),
         Some(1647216000),
-        Some(U128::from(1 * 10u128.pow(24))),
+        Some(U128::from(10u128.pow(24))),
         Some(1),
         None,
         None,
     );
-    let trail_by_id = contract.get_trail_by_id(&"1".into());
+    contract.get_trail_by_id(&"1".into());
     testing_env!(context
         .predecessor_account_id(bob())
         .attached_deposit(ONE_NEAR)
@@ -180,12 +184,12 @@ fn allow_trail_minting_by_whitelisted_address() {
         "CampgroundTest",
         Some(1647109675),
         Some(1647216000),
-        Some(U128::from(1 * 10u128.pow(24))),
+        Some(U128::from(10u128.pow(24))),
         Some(1),
         None,
         None,
     );
-    let trail_by_id = contract.get_trail_by_id(&"1".into());
+    contract.get_trail_by_id(&"1".into());
     testing_env!(context
         .predecessor_account_id(carol())
         .attached_deposit(ONE_NEAR)
@@ -215,7 +219,7 @@ fn test_copies_and_buys_internal(price: u128, attached_deposit: u128) -> Contrac
 
     testing_env!(context
         .predecessor_account_id(bob())
-        .attached_deposit(attached_deposit.into())
+        .attached_deposit(attached_deposit)
         .build());
 
     // Panics
@@ -249,14 +253,35 @@ fn contract_should_return_nft_tokens_total_after_buying() {
 fn contract_should_return_nft_tokens_enumeration_after_buying() {
     let contract = test_copies_and_buys_internal(ONE_NEAR, ONE_NEAR);
     let enumeration = contract.nft_tokens(None, None);
-    let enumeration_unwrap = enumeration.get(0).unwrap();
+    let enumeration_unwrap = enumeration.first().unwrap();
     assert_eq!(enumeration_unwrap.token_id, "1:1");
     assert_eq!(
         enumeration_unwrap.metadata.title.to_owned().unwrap(),
         "CampgroundTest #1"
     );
     assert_eq!(enumeration_unwrap.owner_id, bob());
-    assert_eq!(enumeration_unwrap.series.creator_id, alice());
+    assert!(enumeration_unwrap.approved_account_ids.is_empty());
+
+    let tickets = contract.trail_tickets(None, None);
+    assert_eq!(tickets.first().unwrap().token_id, "1:1");
+    assert_eq!(tickets.first().unwrap().series.creator_id, alice());
+}
+
+#[test]
+fn standard_views_should_return_nep171_tokens() {
+    let contract = test_copies_and_buys_internal(ONE_NEAR, ONE_NEAR);
+    let token = serde_json::to_value(contract.nft_token("1:1".to_string()).unwrap()).unwrap();
+    let mut keys = token.as_object().unwrap().keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(
+        keys,
+        ["approved_account_ids", "metadata", "owner_id", "token_id"]
+    );
+    assert_eq!(token["approved_account_ids"], serde_json::json!({}));
+
+    let ticket = contract.trail_ticket("1:1".to_string()).unwrap();
+    assert_eq!(ticket.series.creator_id, alice());
+    assert!(contract.trail_ticket("1:9".to_string()).is_none());
 }
 
 #[test]
@@ -281,11 +306,14 @@ fn estimate_create_series_storage_usage() {
                 expires_at: None,
                 reference: None,
                 campground_id: "123".to_owned(),
+                media_hash: None,
+                fallback_media: None,
             },
             Some(10000000000000000000000000.into()),
             None,
             None,
             None,
+            None,
         );
 
         let usage = env::storage_usage() - usage;
@@ -300,11 +328,13 @@ fn estimate_create_series_storage_usage() {
         .attached_deposit(STORAGE_FOR_CREATE_SERIES)
         .build());
 
-    measure_create_series(&mut contract, 503);
+    measure_create_series(&mut contract, 633);
 
     for i in 2..200 {
+        // Refresh the context so the prepaid gas covers each call
+        testing_env!(context.build());
         let token_id_len_extra = (i.to_string().len() - 1) * 4;
-        measure_create_series(&mut contract, 433 + token_id_len_extra as u64);
+        measure_create_series(&mut contract, 499 + token_id_len_extra as u64);
     }
 }
 
@@ -357,3 +387,1249 @@ fn should_create_a_non_mintable_trail() {
 
     assert!(!contract.is_trail_mintable(&create.token_id));
 }
+
+fn updated_metadata(title: &str) -> TrailSeriesMetadata {
+    TrailSeriesMetadata {
+        title: title.to_owned(),
+        description: "Updated description".to_owned(),
+        tickets_amount: 1,
+        media: None,
+        data: None,
+        resources: vec![TrailResource {
+            title: None,
+            description: None,
+            media: "http://arweave.net/updated.png".to_owned(),
+            extra: None,
+            reference: None,
+        }],
+        starts_at: None,
+        expires_at: None,
+        reference: None,
+        campground_id: "123".to_owned(),
+        media_hash: None,
+        fallback_media: None,
+    }
+}
+
+#[test]
+fn creator_should_update_trail_metadata_until_frozen() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    assert!(!trail.series.is_frozen);
+
+    contract.update_trail_series_metadata(trail.token_id.clone(), updated_metadata("Updated"));
+    let trail_by_id = contract.get_trail_by_id(&trail.token_id);
+    assert_eq!(trail_by_id.metadata.title, "Updated");
+    assert_eq!(trail_by_id.metadata.tickets_amount, 10);
+    assert!(!contract.is_trail_frozen(&trail.token_id));
+    assert_eq!(
+        get_logs().last().unwrap(),
+        r#"EVENT_JSON:{"standard":"campground","version":"1.0.0","event":"series_metadata_update","data":[{"series_id":"1","creator_id":"alice","updated_at":0}]}"#
+    );
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.freeze_trail_series(trail.token_id.clone());
+    assert!(contract.is_trail_frozen(&trail.token_id));
+    assert_eq!(
+        get_logs(),
+        [
+            r#"EVENT_JSON:{"standard":"campground","version":"1.0.0","event":"series_freeze","data":[{"series_id":"1","creator_id":"alice"}]}"#
+        ]
+    );
+    assert!(contract.get_trail_by_id(&trail.token_id).is_frozen);
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    let token_id = contract.nft_mint(trail.token_id.clone(), bob());
+    let token = contract.trail_ticket(token_id).unwrap();
+    assert!(token.series.is_frozen);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Trail metadata is frozen")]
+fn contract_should_reject_updating_frozen_trail_metadata() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    testing_env!(context.attached_deposit(1).build());
+    contract.freeze_trail_series(trail.token_id.clone());
+
+    contract.update_trail_series_metadata(trail.token_id, updated_metadata("Updated"));
+}
+
+#[test]
+#[should_panic(expected = "Campground: Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_freezing_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    contract.freeze_trail_series(trail.token_id);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only Trail creator can freeze its metadata")]
+fn only_trail_creator_can_freeze_trail_metadata() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.freeze_trail_series(trail.token_id);
+}
+
+#[test]
+fn creator_should_increase_supply_of_sold_out_trail() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(1),
+        None,
+        None,
+    );
+    contract.nft_mint(trail.token_id.clone(), bob());
+    assert!(!contract.get_trail_by_id(&trail.token_id).is_mintable);
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.update_trail_series_supply(trail.token_id.clone(), 2);
+    let trail_by_id = contract.get_trail_by_id(&trail.token_id);
+    assert!(trail_by_id.is_mintable);
+    assert_eq!(trail_by_id.supply.total, 2);
+    assert_eq!(trail_by_id.supply.circulating, 1);
+
+    testing_env!(context.attached_deposit(STORAGE_FOR_CREATE_SERIES).build());
+    assert_eq!(contract.nft_mint(trail.token_id.clone(), bob()), "1:2");
+    assert!(!contract.get_trail_by_id(&trail.token_id).is_mintable);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Total supply cannot be lower than circulating supply")]
+fn contract_should_reject_reducing_supply_below_circulating() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    contract.nft_mint(trail.token_id.clone(), bob());
+    contract.nft_mint(trail.token_id.clone(), bob());
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.update_trail_series_supply(trail.token_id, 1);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Trail is not mintable")]
+fn contract_should_reject_minting_after_sale_is_closed() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    contract.nft_mint(trail.token_id.clone(), bob());
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.close_trail_series_sale(trail.token_id.clone());
+    let trail_by_id = contract.get_trail_by_id(&trail.token_id);
+    assert!(!trail_by_id.is_mintable);
+    assert_eq!(trail_by_id.supply.total, 1);
+
+    testing_env!(context.attached_deposit(STORAGE_FOR_CREATE_SERIES).build());
+    contract.nft_mint(trail.token_id, bob());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only Trail creator can change its supply")]
+fn only_trail_creator_can_change_trail_supply() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.close_trail_series_sale(trail.token_id);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_require_one_yocto_to_change_trail_supply() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    contract.update_trail_series_supply(trail.token_id, 20);
+}
+
+#[test]
+fn supply_update_should_not_enable_trails_disabled_for_users() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(1),
+        None,
+        Some(false),
+    );
+    contract.nft_mint(trail.token_id.clone(), bob());
+    assert!(!contract.get_trail_by_id(&trail.token_id).is_mintable);
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.update_trail_series_supply(trail.token_id.clone(), 2);
+    assert!(!contract.is_trail_mintable(&trail.token_id));
+    assert_eq!(contract.get_trail_by_id(&trail.token_id).supply.total, 2);
+
+    // The creator can still mint the copies added back
+    testing_env!(context.attached_deposit(STORAGE_FOR_CREATE_SERIES).build());
+    contract.nft_mint(trail.token_id.clone(), carol());
+    let trail_by_id = contract.get_trail_by_id(&trail.token_id);
+    assert_eq!(trail_by_id.supply.circulating, 2);
+    assert!(!trail_by_id.is_mintable);
+}
+
+#[test]
+fn creator_should_update_trail_price() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.update_trail_series_price(trail.token_id.clone(), (2 * ONE_NEAR).into());
+    let trail_by_id = contract.get_trail_by_id(&trail.token_id);
+    assert_eq!(trail_by_id.price.0, 2 * ONE_NEAR);
+    assert_eq!(
+        trail_by_id.campground_fee_near.0,
+        2 * ONE_NEAR * contract.campground_fee as u128 / 100
+    );
+    assert_eq!(
+        get_logs().last().unwrap(),
+        r#"EVENT_JSON:{"standard":"campground","version":"1.0.0","event":"series_price_update","data":[{"series_id":"1","previous_price":"1000000000000000000000000","price":"2000000000000000000000000"}]}"#
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only Trail creator can change its price")]
+fn only_trail_creator_can_change_trail_price() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.update_trail_series_price(trail.token_id, 0.into());
+}
+
+#[test]
+fn creator_should_change_price_of_frozen_trail() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+    testing_env!(context.attached_deposit(1).build());
+    contract.freeze_trail_series(trail.token_id.clone());
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.update_trail_series_price(trail.token_id.clone(), 0.into());
+    assert_eq!(contract.get_trail_by_id(&trail.token_id).price.0, 0);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_require_one_yocto_to_change_trail_price() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    contract.update_trail_series_price(trail.token_id, 0.into());
+}
+
+#[test]
+fn trail_creation_should_emit_series_create_event() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(1000.into()),
+        Some(10),
+        None,
+        Some(false),
+    );
+    assert_eq!(
+        get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"campground","version":"1.0.0","event":"series_create","data":[{"series_id":"1","creator_id":"alice","price":"1000","total":10,"is_mintable":false}]}"#
+        ]
+    );
+}
+
+#[test]
+fn creator_should_delete_trail_without_copies() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        Some(false),
+    );
+    testing_env!(context.attached_deposit(1).build());
+    contract.freeze_trail_series(trail.token_id.clone());
+    let usage = env::storage_usage();
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.delete_trail_series(trail.token_id.clone());
+
+    assert!(env::storage_usage() < usage);
+    assert!(!contract.series_exists(&trail.token_id));
+    assert!(contract.is_trail_mintable(&trail.token_id));
+    assert!(!contract.frozen_trails.contains(&trail.token_id));
+    assert!(contract
+        .get_all_trails_by_creator(&alice(), Some(true))
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only trails without circulating copies can be deleted")]
+fn contract_should_reject_deleting_trail_with_copies() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    contract.nft_mint(trail.token_id.clone(), bob());
+
+    testing_env!(context.attached_deposit(1).build());
+    contract.delete_trail_series(trail.token_id);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_require_one_yocto_to_delete_trail() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    contract.delete_trail_series(trail.token_id);
+}
+
+#[test]
+fn archived_trails_should_be_hidden_from_listings() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    let token_id = contract.nft_mint(trail.token_id.clone(), bob());
+
+    contract.archive_trail_series(trail.token_id.clone());
+    assert!(contract.is_trail_archived(&trail.token_id));
+    assert!(contract.get_trail_by_id(&trail.token_id).is_archived);
+    assert!(contract
+        .get_all_trails_by_creator(&alice(), None)
+        .is_empty());
+    assert!(contract.get_all_trails_by_owner(&bob(), None).is_empty());
+    assert_eq!(
+        contract
+            .get_all_trails_by_creator(&alice(), Some(true))
+            .len(),
+        1
+    );
+
+    let token = contract.trail_ticket(token_id).unwrap();
+    assert_eq!(token.owner_id, bob());
+    assert!(token.series.is_archived);
+
+    contract.unarchive_trail_series(trail.token_id.clone());
+    assert!(!contract.is_trail_archived(&trail.token_id));
+    assert_eq!(contract.get_all_trails_by_creator(&alice(), None).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Trail is archived")]
+fn contract_should_reject_minting_archived_trail() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    contract.archive_trail_series(trail.token_id.clone());
+
+    contract.nft_mint(trail.token_id, bob());
+}
+
+fn create_series_with_slug(contract: &mut Contract, slug: &str) -> JsonTrail {
+    contract.create_trail_series(
+        updated_metadata("Slugged"),
+        None,
+        None,
+        None,
+        None,
+        Some(slug.to_owned()),
+    )
+}
+
+#[test]
+fn trail_ids_should_not_collide_after_deleting_trails() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    for _ in 0..3 {
+        create_series(
+            &mut contract,
+            "CampgroundTest",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+    testing_env!(context.attached_deposit(1).build());
+    contract.delete_trail_series("2".into());
+    testing_env!(context.attached_deposit(STORAGE_FOR_CREATE_SERIES).build());
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(trail.token_id, "4");
+    assert!(!contract.series_exists(&"2".into()));
+}
+
+#[test]
+fn creator_should_create_trail_with_slug() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = create_series_with_slug(&mut contract, "yosemite-2022");
+    assert_eq!(trail.token_id, "yosemite-2022");
+    assert_eq!(
+        contract.get_trail_by_id(&"yosemite-2022".into()).creator_id,
+        alice()
+    );
+
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(trail.token_id, "1");
+
+    testing_env!(context.attached_deposit(ONE_NEAR).build());
+    assert_eq!(
+        contract.nft_mint("yosemite-2022".into(), bob()),
+        "yosemite-2022:1"
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: Duplicate series id (E202)")]
+fn contract_should_reject_duplicate_slug() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    create_series_with_slug(&mut contract, "yosemite");
+    create_series_with_slug(&mut contract, "yosemite");
+}
+
+#[test]
+#[should_panic(expected = "Campground: Slug cannot contain `:`")]
+fn contract_should_reject_slug_with_trail_delimeter() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    create_series_with_slug(&mut contract, "yosemite:1");
+}
+
+#[test]
+#[should_panic(expected = "Campground: Slug cannot be a number")]
+fn contract_should_reject_numeric_slug() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    create_series_with_slug(&mut contract, "42");
+}
+
+#[test]
+fn trails_should_be_paginated() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    for title in ["First", "Second", "Third"] {
+        let trail = create_series(
+            &mut contract,
+            title,
+            None,
+            None,
+            Some(0.into()),
+            None,
+            None,
+            None,
+        );
+        contract.nft_mint(trail.token_id.clone(), bob());
+        contract.nft_mint(trail.token_id, bob());
+    }
+
+    testing_env!(context.predecessor_account_id(carol()).build());
+    create_series(&mut contract, "Fourth", None, None, None, None, None, None);
+
+    let ids = |trails: Vec<JsonTrailSeries>| {
+        trails
+            .into_iter()
+            .map(|trail| trail.series_id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(contract.get_trails_count(), 4);
+    assert_eq!(
+        ids(contract.get_trails(None, None, None)),
+        ["1", "2", "3", "4"]
+    );
+    assert_eq!(
+        ids(contract.get_trails(Some(U128(1)), Some(2), None)),
+        ["2", "3"]
+    );
+    assert!(contract.get_trails(Some(U128(4)), None, None).is_empty());
+    assert!(contract
+        .get_trails(Some(U128(u128::MAX)), None, None)
+        .is_empty());
+
+    assert_eq!(contract.get_trails_count_by_creator(&alice()), 3);
+    assert_eq!(contract.get_trails_count_by_creator(&bob()), 0);
+    assert_eq!(
+        ids(contract.get_trails_by_creator(&alice(), Some(U128(2)), Some(10), None)),
+        ["3"]
+    );
+    assert_eq!(
+        ids(contract.get_trails_by_creator(&carol(), None, None, None)),
+        ["4"]
+    );
+    assert!(contract
+        .get_trails_by_creator(&bob(), None, None, None)
+        .is_empty());
+
+    assert_eq!(contract.get_trails_count_by_owner(&bob()), 3);
+    assert_eq!(contract.get_trails_count_by_owner(&carol()), 0);
+    assert_eq!(
+        ids(contract.get_trails_by_owner(&bob(), None, Some(2), None)),
+        ["1", "2"]
+    );
+    assert_eq!(
+        ids(contract.get_trails_by_owner(&bob(), Some(U128(2)), None, None)),
+        ["3"]
+    );
+
+    testing_env!(context.predecessor_account_id(alice()).build());
+    contract.archive_trail_series("2".to_string());
+    assert_eq!(contract.get_trails_count(), 4);
+    assert_eq!(ids(contract.get_trails(None, Some(2), None)), ["1", "3"]);
+    assert_eq!(
+        ids(contract.get_trails(Some(U128(2)), Some(2), None)),
+        ["4"]
+    );
+    assert!(contract.get_trails(Some(U128(3)), None, None).is_empty());
+    assert_eq!(
+        ids(contract.get_trails_by_creator(&alice(), Some(U128(1)), Some(1), None)),
+        ["3"]
+    );
+    assert_eq!(
+        ids(contract.get_trails(None, Some(2), Some(true))),
+        ["1", "2"]
+    );
+    assert_eq!(
+        ids(contract.get_trails_by_owner(&bob(), None, None, None)),
+        ["1", "3"]
+    );
+}
+
+#[test]
+fn owner_trails_should_follow_transfers() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    for title in ["First", "Second"] {
+        let trail = create_series(
+            &mut contract,
+            title,
+            None,
+            None,
+            Some(0.into()),
+            None,
+            None,
+            None,
+        );
+        contract.nft_mint(trail.token_id.clone(), bob());
+        contract.nft_mint(trail.token_id, bob());
+    }
+
+    let ids = |trails: Vec<JsonTrailSeries>| {
+        trails
+            .into_iter()
+            .map(|trail| trail.series_id)
+            .collect::<Vec<_>>()
+    };
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.nft_transfer(carol(), "1:1".to_string(), None);
+    assert_eq!(contract.get_trails_count_by_owner(&bob()), 2);
+    assert_eq!(
+        ids(contract.get_trails_by_owner(&carol(), None, None, None)),
+        ["1"]
+    );
+
+    // Giving away the last copy moves the last listed trail into its place
+    contract.nft_transfer(carol(), "1:2".to_string(), None);
+    assert_eq!(
+        ids(contract.get_trails_by_owner(&bob(), None, None, None)),
+        ["2"]
+    );
+    assert_eq!(contract.get_trails_count_by_owner(&carol()), 1);
+    assert_eq!(
+        contract.get_owned_copies_count(&"1".to_string(), &carol()),
+        2
+    );
+}
+
+#[test]
+fn series_tokens_should_be_listed_in_mint_order() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let first = create_series(
+        &mut contract,
+        "First",
+        None,
+        None,
+        Some(0.into()),
+        None,
+        None,
+        None,
+    );
+    let second = create_series(
+        &mut contract,
+        "Second",
+        None,
+        None,
+        Some(0.into()),
+        None,
+        None,
+        None,
+    );
+    for receiver in [bob(), carol(), bob()] {
+        contract.nft_mint(first.token_id.clone(), receiver);
+    }
+    contract.nft_mint(second.token_id.clone(), carol());
+
+    let token_ids = |tokens: Vec<JsonTrail>| {
+        tokens
+            .into_iter()
+            .map(|token| token.token_id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        contract.nft_supply_for_series(first.token_id.clone()),
+        U128(3)
+    );
+    assert_eq!(
+        contract.nft_supply_for_series(second.token_id.clone()),
+        U128(1)
+    );
+    assert_eq!(contract.nft_supply_for_series("3".to_string()), U128(0));
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_series(first.token_id.clone(), None, None)),
+        ["1:1", "1:2", "1:3"]
+    );
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_series(
+            first.token_id.clone(),
+            Some(U128(1)),
+            Some(1)
+        )),
+        ["1:2"]
+    );
+    assert!(contract
+        .nft_tokens_for_series("3".to_string(), None, None)
+        .is_empty());
+
+    let holders = contract.nft_holders_for_series(first.token_id, None, None);
+    assert_eq!(
+        holders
+            .iter()
+            .map(|holder| (holder.account_id.clone(), holder.copies))
+            .collect::<Vec<_>>(),
+        [(bob(), 2), (carol(), 1)]
+    );
+    assert!(contract
+        .nft_holders_for_series(second.token_id, Some(U128(1)), None)
+        .is_empty());
+}
+
+#[test]
+fn copies_should_carry_edition_and_validity_metadata() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .block_timestamp(1_000_000_000_000)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        Some(1647109675000),
+        Some(1647216000000),
+        Some(0.into()),
+        Some(10),
+        None,
+        None,
+    );
+    let token_id = contract.nft_mint(trail.token_id.clone(), bob());
+
+    let metadata = contract.nft_token(token_id.clone()).unwrap().metadata;
+    assert_eq!(metadata.copies, Some(10));
+    assert_eq!(metadata.issued_at, Some(1_000_000));
+    assert_eq!(metadata.starts_at, Some(1647109675000));
+    assert_eq!(metadata.expires_at, Some(1647216000000));
+    assert_eq!(metadata.updated_at, None);
+    assert_eq!(metadata.extra, None);
+    assert_eq!(metadata.reference, None);
+
+    testing_env!(context.block_timestamp(2_000_000_000_000).build());
+    contract.update_trail_series_metadata(
+        trail.token_id,
+        TrailSeriesMetadata {
+            data: Some(r#"{"difficulty":"hard"}"#.to_owned()),
+            reference: Some("https://campground.example/trail.json".to_owned()),
+            expires_at: Some(1700000000000),
+            ..updated_metadata("Updated")
+        },
+    );
+
+    let metadata = contract.nft_token(token_id).unwrap().metadata;
+    assert_eq!(metadata.title, Some("Updated #1".to_owned()));
+    assert_eq!(metadata.issued_at, Some(1_000_000));
+    assert_eq!(metadata.starts_at, None);
+    assert_eq!(metadata.expires_at, Some(1700000000000));
+    assert_eq!(metadata.updated_at, Some(2_000_000));
+    assert_eq!(metadata.extra, Some(r#"{"difficulty":"hard"}"#.to_owned()));
+    assert_eq!(
+        metadata.reference,
+        Some("https://campground.example/trail.json".to_owned())
+    );
+}
+
+#[test]
+fn series_fallback_media_should_override_configured_one() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let trail = contract.create_trail_series(
+        TrailSeriesMetadata {
+            fallback_media: Some("https://example.com/fallback.png".to_owned()),
+            ..updated_metadata("With fallback")
+        },
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        trail.metadata.media,
+        Some("https://example.com/fallback.png".to_owned())
+    );
+
+    let trail = contract.create_trail_series(
+        TrailSeriesMetadata {
+            media: Some("https://example.com/media.png".to_owned()),
+            fallback_media: Some("https://example.com/fallback.png".to_owned()),
+            ..updated_metadata("With media")
+        },
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        trail.metadata.media,
+        Some("https://example.com/media.png".to_owned())
+    );
+}
+
+#[test]
+fn relative_uris_should_be_resolved_against_base_uri() {
+    let mut context = get_context(owner());
+    testing_env!(context.build());
+    let mut contract = Contract::new(
+        owner(),
+        NFTContractMetadata {
+            spec: "nft-1.0.0".to_owned(),
+            name: "Campground NFT Contract".to_owned(),
+            symbol: "CMPGRND".to_owned(),
+            icon: None,
+            base_uri: Some("https://arweave.net/".to_owned()),
+            reference: None,
+            reference_hash: None,
+        },
+        treasury(),
+    );
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = contract.create_trail_series(
+        TrailSeriesMetadata {
+            media: Some("/media.png".to_owned()),
+            reference: Some("reference.json".to_owned()),
+            ..updated_metadata("Relative")
+        },
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        trail.metadata.media,
+        Some("https://arweave.net/media.png".to_owned())
+    );
+    assert_eq!(
+        trail.metadata.reference,
+        Some("https://arweave.net/reference.json".to_owned())
+    );
+
+    let trail = contract.create_trail_series(
+        TrailSeriesMetadata {
+            media: Some("ipfs://bafy/media.png".to_owned()),
+            ..updated_metadata("Absolute")
+        },
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        trail.metadata.media,
+        Some("ipfs://bafy/media.png".to_owned())
+    );
+}
+
+#[test]
+fn media_hash_should_be_exposed_on_copies() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    let media_hash = Base64VecU8::from(env::sha256(b"media"));
+    let trail = contract.create_trail_series(
+        TrailSeriesMetadata {
+            media: Some("https://example.com/media.png".to_owned()),
+            media_hash: Some(media_hash.clone()),
+            ..updated_metadata("Hashed")
+        },
+        Some(0.into()),
+        None,
+        None,
+        None,
+        None,
+    );
+    contract.nft_mint(trail.token_id, bob());
+
+    let token = contract.nft_token("1:1".to_string()).unwrap();
+    assert_eq!(token.metadata.media_hash, Some(media_hash));
+}
+
+#[test]
+#[should_panic(expected = "Campground: Media hash must be a base64-encoded SHA-256 hash")]
+fn contract_should_reject_media_hash_of_wrong_length() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    contract.create_trail_series(
+        TrailSeriesMetadata {
+            media: Some("https://example.com/media.png".to_owned()),
+            media_hash: Some(b"not a sha256".to_vec().into()),
+            ..updated_metadata("Hashed")
+        },
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: Media hash cannot be set without media")]
+fn contract_should_reject_media_hash_without_media() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    contract.create_trail_series(
+        TrailSeriesMetadata {
+            media_hash: Some(env::sha256(b"media").into()),
+            ..updated_metadata("Hashed")
+        },
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+}
+
+fn assert_rejected(metadata: TrailSeriesMetadata) {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+
+    contract.create_trail_series(metadata, None, None, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Campground: description cannot be longer than 4096 bytes (E222)")]
+fn contract_should_reject_too_long_description() {
+    assert_rejected(TrailSeriesMetadata {
+        description: "a".repeat(4097),
+        ..updated_metadata("Long")
+    });
+}
+
+#[test]
+#[should_panic(expected = "Campground: resource extra cannot be longer than 8192 bytes (E222)")]
+fn contract_should_reject_too_long_resource_extra() {
+    let mut metadata = updated_metadata("Long");
+    metadata.resources[0].extra = Some("a".repeat(8193));
+    assert_rejected(metadata);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Trails cannot have more than 32 resources (E223)")]
+fn contract_should_reject_too_many_resources() {
+    let mut metadata = updated_metadata("Crowded");
+    metadata.resources = vec![metadata.resources[0].clone(); 33];
+    assert_rejected(metadata);
+}
+
+#[test]
+#[should_panic(expected = "Campground: media must be a relative path or use one of the schemes")]
+fn contract_should_reject_unsupported_media_scheme() {
+    assert_rejected(TrailSeriesMetadata {
+        media: Some("javascript:alert(1)".to_owned()),
+        ..updated_metadata("Scheme")
+    });
+}
+
+#[test]
+#[should_panic(expected = "Campground: Campground id must have between 1 and 64")]
+fn contract_should_reject_invalid_campground_id() {
+    assert_rejected(TrailSeriesMetadata {
+        campground_id: "campground 1".to_owned(),
+        ..updated_metadata("Campground")
+    });
+}
+
+#[test]
+#[should_panic(expected = "Campground: resource reference must be a relative path")]
+fn metadata_updates_should_be_validated() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail =
+        contract.create_trail_series(updated_metadata("Valid"), None, None, None, None, None);
+
+    let mut metadata = updated_metadata("Invalid");
+    metadata.resources[0].reference = Some("file:///etc/passwd".to_owned());
+    contract.update_trail_series_metadata(trail.token_id, metadata);
+}
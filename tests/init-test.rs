@@ -2,10 +2,23 @@
 
 pub mod context;
 
-use ito_contract::{bridge::SeriesBridge, Contract};
-use near_sdk::testing_env;
+use ito_contract::{
+    bridge::SeriesBridge,
+    enumeration::NonFungibleTokenEnumeration,
+    migration::{MigrationBridge, MigrationStep},
+    nft_core::NonFungibleTokenCore,
+    Contract, SeriesSupply, StorageKey, TokenMetadata, TrailResource,
+};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::U128;
+use near_sdk::{env, test_utils::get_logs, testing_env, AccountId, Balance};
+use std::collections::HashMap;
 
-use context::{alice, get_context, owner, treasury};
+use context::{
+    alice, bob, carol, create_series, get_context, owner, setup_contract, treasury,
+    STORAGE_FOR_CREATE_SERIES,
+};
 
 #[test]
 fn contract_should_be_initialized_with_default_values() {
@@ -20,3 +33,401 @@ fn contract_should_be_initialized_with_default_values() {
     assert_eq!(contract.campground_treasury_address, treasury());
     assert_eq!(contract.campground_minimum_fee_yocto_near, 10u128.pow(23));
 }
+
+/// State layout up to `v3`, as written by the deployed contract.
+#[derive(BorshSerialize)]
+struct ContractV3 {
+    owner_id: AccountId,
+    tokens_per_owner: LookupMap<AccountId, UnorderedSet<String>>,
+    tokens_by_id: LookupMap<String, TrailBusinessV3>,
+    token_metadata_by_id: UnorderedMap<String, String>,
+    trails_metadata_by_id: UnorderedMap<String, TrailSeriesV3>,
+    trails_series_by_creator: LookupMap<AccountId, UnorderedSet<String>>,
+    nonmintable_trails: LookupSet<String>,
+    metadata: LazyOption<ito_contract::NFTContractMetadata>,
+    campground_fee: u64,
+    campground_treasury_address: AccountId,
+    campground_minimum_fee_yocto_near: Balance,
+    settings: UnorderedMap<String, String>,
+}
+
+/// Token layout up to `v3`.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TrailBusinessV3 {
+    owner_id: AccountId,
+    token_id: String,
+    partial_metadata: TokenMetadata,
+}
+
+/// Series metadata layout up to `v3`.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TrailSeriesMetadataV3 {
+    title: String,
+    description: String,
+    tickets_amount: u64,
+    media: Option<String>,
+    data: Option<String>,
+    resources: Vec<TrailResource>,
+    starts_at: Option<u64>,
+    expires_at: Option<u64>,
+    reference: Option<String>,
+    campground_id: String,
+}
+
+/// Series layout up to `v3`.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TrailSeriesV3 {
+    is_mintable: bool,
+    creator_id: AccountId,
+    issue_at: u64,
+    metadata: TrailSeriesMetadataV3,
+    supply: SeriesSupply,
+    price: U128,
+    campground_fee_near: U128,
+    creator_royalty_near: Option<U128>,
+    royalties: HashMap<AccountId, u32>,
+}
+
+/// Writes `contract` as the `v3` layout, with tokens carrying their series metadata
+/// and series without a media hash nor fallback media.
+fn write_v3_state(mut contract: Contract) {
+    let mut tokens_by_id: LookupMap<String, TrailBusinessV3> =
+        LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap());
+    for token_id in contract.token_metadata_by_id.keys() {
+        let token = contract.tokens_by_id.remove(&token_id).unwrap();
+        let series = contract.get_trail_by_id(&token.token_id);
+        tokens_by_id.insert(
+            &token_id,
+            &TrailBusinessV3 {
+                owner_id: token.owner_id,
+                token_id: token.token_id,
+                partial_metadata: TokenMetadata {
+                    title: Some(series.metadata.title),
+                    description: Some(series.metadata.description),
+                    media: series.metadata.media,
+                    media_hash: None,
+                    copies: None,
+                    issued_at: None,
+                    expires_at: None,
+                    starts_at: None,
+                    updated_at: None,
+                    extra: None,
+                    reference: None,
+                    reference_hash: None,
+                },
+            },
+        );
+    }
+
+    let trails = contract.trails_metadata_by_id.to_vec();
+    contract.trails_metadata_by_id.clear();
+    let mut trails_metadata_by_id: UnorderedMap<String, TrailSeriesV3> =
+        UnorderedMap::try_from_slice(&contract.trails_metadata_by_id.try_to_vec().unwrap())
+            .unwrap();
+    for (trail_id, series) in trails {
+        let metadata = series.metadata;
+        trails_metadata_by_id.insert(
+            &trail_id,
+            &TrailSeriesV3 {
+                is_mintable: series.is_mintable,
+                creator_id: series.creator_id,
+                issue_at: series.issue_at,
+                metadata: TrailSeriesMetadataV3 {
+                    title: metadata.title,
+                    description: metadata.description,
+                    tickets_amount: metadata.tickets_amount,
+                    media: metadata.media,
+                    data: metadata.data,
+                    resources: metadata.resources,
+                    starts_at: metadata.starts_at,
+                    expires_at: metadata.expires_at,
+                    reference: metadata.reference,
+                    campground_id: metadata.campground_id,
+                },
+                supply: series.supply,
+                price: series.price,
+                campground_fee_near: series.campground_fee_near,
+                creator_royalty_near: series.creator_royalty_near,
+                royalties: series.royalties,
+            },
+        );
+    }
+
+    env::state_write(&ContractV3 {
+        owner_id: contract.owner_id,
+        tokens_per_owner: contract.tokens_per_owner,
+        tokens_by_id,
+        token_metadata_by_id: contract.token_metadata_by_id,
+        trails_metadata_by_id,
+        trails_series_by_creator: contract.trails_series_by_creator,
+        nonmintable_trails: contract.nonmintable_trails,
+        metadata: contract.metadata,
+        campground_fee: contract.campground_fee,
+        campground_treasury_address: contract.campground_treasury_address,
+        campground_minimum_fee_yocto_near: contract.campground_minimum_fee_yocto_near,
+        settings: UnorderedMap::new(b"settings".to_vec()),
+    });
+}
+
+/// Migrates the state written by `write_v3_state`, running batches of `limit` until complete.
+/// The context must have the contract `owner` as predecessor, attaching 1 yoctoNEAR.
+fn migrate_v3_to_v4(limit: Option<u64>) -> Contract {
+    let mut contract = Contract::migrate_v3_to_v4();
+    while !contract.migrate_v4_batch(limit) {}
+    assert_eq!(contract.get_migration_step(), None);
+    contract
+}
+
+#[test]
+fn migration_should_rebuild_trails_by_creator() {
+    let (mut context, mut contract) = setup_contract();
+    for creator in [alice(), bob()] {
+        testing_env!(context
+            .predecessor_account_id(creator)
+            .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+            .build());
+        create_series(
+            &mut contract,
+            "CampgroundTest",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Up to `v3`, every creator was given a copy of the same set of trails
+    let mut shared_set = UnorderedSet::new(StorageKey::TokenPerCreator.try_to_vec().unwrap());
+    for (creator, trail_id) in [(alice(), "1"), (bob(), "2")] {
+        contract
+            .trails_series_by_creator
+            .remove(&creator)
+            .unwrap()
+            .clear();
+        shared_set.insert(&trail_id.to_string());
+        contract
+            .trails_series_by_creator
+            .insert(&creator, &shared_set);
+    }
+    assert_eq!(contract.get_all_trails_by_creator(&bob(), None).len(), 2);
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    write_v3_state(contract);
+
+    let contract = migrate_v3_to_v4(Some(1));
+    for creator in [alice(), bob()] {
+        let trails = contract.get_all_trails_by_creator(&creator, None);
+        assert_eq!(trails.len(), 1);
+        assert_eq!(trails[0].creator_id, creator);
+    }
+}
+
+#[test]
+fn migration_should_index_tokens_by_series_and_owner() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        None,
+        None,
+        None,
+    );
+    for receiver in [bob(), carol(), bob()] {
+        contract.nft_mint(trail.token_id.clone(), receiver);
+    }
+
+    // Up to `v3`, tokens were not indexed by series
+    contract
+        .tokens_per_series
+        .remove(&trail.token_id)
+        .unwrap()
+        .clear();
+    contract
+        .holders_per_series
+        .remove(&trail.token_id)
+        .unwrap()
+        .clear();
+    for owner in [bob(), carol()] {
+        contract
+            .tokens_per_owner_by_mint
+            .remove(&owner)
+            .unwrap()
+            .clear();
+    }
+    for token_id in ["1:1", "1:2", "1:3"] {
+        contract.token_mint_index.remove(&token_id.to_string());
+    }
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    write_v3_state(contract);
+
+    let contract = migrate_v3_to_v4(None);
+    assert_eq!(
+        contract.nft_supply_for_series(trail.token_id.clone()),
+        3.into()
+    );
+    assert_eq!(
+        contract
+            .nft_holders_for_series(trail.token_id, None, None)
+            .iter()
+            .map(|holder| (holder.account_id.clone(), holder.copies))
+            .collect::<Vec<_>>(),
+        [(bob(), 2), (carol(), 1)]
+    );
+    assert_eq!(
+        contract
+            .trail_tickets_for_owner_after(bob(), Some("1:1".to_string()), None)
+            .iter()
+            .map(|token| token.token_id.clone())
+            .collect::<Vec<_>>(),
+        ["1:3"]
+    );
+}
+
+#[test]
+fn migration_should_compact_tokens() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        None,
+        None,
+        None,
+    );
+    for receiver in [bob(), carol()] {
+        contract.nft_mint(trail.token_id.clone(), receiver);
+    }
+    let token_before = contract.nft_token("1:2".to_string()).unwrap();
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    write_v3_state(contract);
+    let storage_usage = env::storage_usage();
+
+    let contract = migrate_v3_to_v4(None);
+    // The series itself grows by the two `None` tags of `media_hash` and `fallback_media`.
+    let freed = storage_usage + 2 - env::storage_usage();
+    assert!(freed > 0);
+    assert!(get_logs().contains(&format!(
+        "Campground: Compacted 2 tokens, freeing {} bytes",
+        freed
+    )));
+
+    let token = contract.tokens_by_id.get(&"1:2".to_string()).unwrap();
+    assert_eq!(token.owner_id, carol());
+    assert_eq!(token.token_id, trail.token_id);
+    assert_eq!(token.copy_number, 2);
+    assert_eq!(token.issued_at, None);
+
+    let series = contract.get_trail_by_id(&trail.token_id);
+    assert!(series.metadata.media_hash.is_none());
+    assert!(series.metadata.fallback_media.is_none());
+
+    let token_after = contract.nft_token("1:2".to_string()).unwrap();
+    assert_eq!(token_after.owner_id, token_before.owner_id);
+    assert_eq!(token_after.metadata.title, token_before.metadata.title);
+    assert_eq!(token_after.metadata.media, token_before.metadata.media);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Migration is in progress, see `migrate_v4_batch` (E512)")]
+fn trails_should_not_be_read_while_migrating() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    write_v3_state(contract);
+
+    let mut contract = Contract::migrate_v3_to_v4();
+    assert!(!contract.migrate_v4_batch(Some(1)));
+    assert_eq!(
+        contract.get_migration_step(),
+        Some(MigrationStep::DetachCreators)
+    );
+    contract.get_trail_by_id(&trail.token_id);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner can execute (E101)")]
+fn migration_batches_should_only_be_run_by_owner() {
+    let (mut context, contract) = setup_contract();
+    write_v3_state(contract);
+
+    let mut contract = Contract::migrate_v3_to_v4();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.migrate_v4_batch(None);
+}
+
+#[test]
+fn creators_should_only_list_their_own_trails() {
+    let (mut context, mut contract) = setup_contract();
+    for creator in [alice(), bob(), alice()] {
+        testing_env!(context
+            .predecessor_account_id(creator)
+            .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+            .build());
+        create_series(
+            &mut contract,
+            "CampgroundTest",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    let creators_of = |creator| {
+        contract
+            .get_all_trails_by_creator(&creator, None)
+            .iter()
+            .map(|trail| trail.creator_id.clone())
+            .collect::<Vec<AccountId>>()
+    };
+    assert_eq!(creators_of(alice()), vec![alice(), alice()]);
+    assert_eq!(creators_of(bob()), vec![bob()]);
+}
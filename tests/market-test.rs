@@ -1,9 +1,12 @@
 pub mod context;
 
 use ito_contract::{admin::AdminBridge, bridge::SeriesBridge, ONE_NEAR};
-use near_sdk::testing_env;
+use near_sdk::{serde_json, test_utils::get_logs, testing_env};
 
-use context::{alice, bob, carol, create_series, owner, setup_contract, STORAGE_FOR_CREATE_SERIES};
+use context::{
+    alice, bob, carol, create_series, execute_pending_admin_changes, owner, setup_contract,
+    STORAGE_FOR_CREATE_SERIES,
+};
 
 #[test]
 #[should_panic(
@@ -127,8 +130,8 @@ fn contract_should_allow_account_to_buy_with_one_near() {
     let get_account_trails = contract.tokens_per_owner.get(&carol()).unwrap();
     let trails_as_vec = get_account_trails.to_vec();
     assert_eq!(get_account_trails.len(), 2);
-    assert_eq!(trails_as_vec.get(0).unwrap(), &"1:1".clone());
-    assert_eq!(trails_as_vec.get(1).unwrap(), &"1:2".clone());
+    assert_eq!(trails_as_vec.first().unwrap(), &"1:1");
+    assert_eq!(trails_as_vec.get(1).unwrap(), &"1:2");
 
     let trails_by_id = contract.tokens_by_id.get(&"1:1".into()).unwrap();
     assert_eq!(trails_by_id.owner_id, carol());
@@ -137,14 +140,17 @@ fn contract_should_allow_account_to_buy_with_one_near() {
     assert!(contract.is_owner(&"1".into(), &carol()));
     assert!(!(contract.is_owner(&"1".into(), &bob())));
     assert!(!(contract.is_owner(&"2".into(), &carol())));
+    assert_eq!(contract.get_owned_copies_count(&"1".into(), &carol()), 2);
+    assert_eq!(contract.get_owned_copies_count(&"1".into(), &bob()), 0);
+    assert_eq!(contract.get_owned_copies_count(&"2".into(), &carol()), 0);
 
-    let get_trails_by_owner = contract.get_all_trails_by_owner(&carol());
+    let get_trails_by_owner = contract.get_all_trails_by_owner(&carol(), None);
     assert_eq!(get_trails_by_owner.len(), 1);
-    let data = get_trails_by_owner.get(0).unwrap();
+    let data = get_trails_by_owner.first().unwrap();
     assert_eq!(data.creator_id, alice());
 
     // Re run test to verify ownership
-    let get_trails_by_owner = contract.get_all_trails_by_owner(&carol());
+    let get_trails_by_owner = contract.get_all_trails_by_owner(&carol(), None);
     assert_eq!(get_trails_by_owner.len(), 1);
 }
 
@@ -154,10 +160,12 @@ fn contract_should_accept_when_buying_with_campground_fee_greater_than_100() {
 
     testing_env!(context
         .predecessor_account_id(owner())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
     contract.change_campground_minimum_fee(0);
-    contract.change_campground_fee(120);
+    execute_pending_admin_changes(&mut context, &mut contract);
+    // Fees above 100 can no longer be set, but were accepted before
+    contract.campground_fee = 120;
 
     testing_env!(context
         .predecessor_account_id(alice())
@@ -188,10 +196,11 @@ fn contract_should_accepts_when_campground_fee_is_zero() {
 
     testing_env!(context
         .predecessor_account_id(owner())
-        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .attached_deposit(1)
         .build());
     contract.change_campground_minimum_fee(0);
     contract.change_campground_fee(0);
+    execute_pending_admin_changes(&mut context, &mut contract);
 
     testing_env!(context
         .predecessor_account_id(alice())
@@ -243,3 +252,85 @@ fn contract_should_reject_when_trail_is_not_mintable() {
 
     contract.nft_buy_series("1".to_string(), carol());
 }
+
+#[test]
+fn contract_should_log_purchase_breakdown_when_buying() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    contract.nft_buy_series(trail.token_id, carol());
+
+    let fee = contract.get_trail_by_id(&"1".into()).campground_fee_near.0;
+    assert_eq!(
+        get_logs().last().unwrap(),
+        &format!(
+            r#"EVENT_JSON:{{"standard":"campground","version":"1.0.0","event":"series_purchase","data":[{{"buyer_id":"bob","receiver_id":"carol","series_id":"1","token_id":"1:1","amount":"{}","campground_fee":"{}","creator_proceeds":"{}","payment_method":"Near","storage_deposit":"0","refund":"0"}}]}}"#,
+            ONE_NEAR,
+            fee,
+            ONE_NEAR - fee
+        )
+    );
+}
+
+#[test]
+fn contract_should_log_purchase_with_refund_when_minting() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    for (minter, method) in [(alice(), "CreatorMinted"), (carol(), "FiatMinted")] {
+        testing_env!(context
+            .predecessor_account_id(minter.clone())
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.nft_mint(trail.token_id.clone(), bob());
+
+        let logs = get_logs();
+        assert!(logs[0].contains(r#""event":"nft_mint""#));
+        assert!(logs[0].contains(&format!(r#""memo":"{{\"price\":\"{}\"}}""#, ONE_NEAR)));
+
+        let event: serde_json::Value =
+            serde_json::from_str(logs[1].strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+        let data = &event["data"][0];
+        assert_eq!(event["event"], "series_purchase");
+        assert_eq!(data["buyer_id"], minter.to_string());
+        assert_eq!(data["receiver_id"], "bob");
+        assert_eq!(data["series_id"], "1");
+        assert_eq!(data["amount"], "0");
+        assert_eq!(data["campground_fee"], "0");
+        assert_eq!(data["creator_proceeds"], "0");
+        assert_eq!(data["payment_method"], method);
+        assert_eq!(data["storage_deposit"], ONE_NEAR.to_string());
+        let refund: u128 = data["refund"].as_str().unwrap().parse().unwrap();
+        assert!(refund > 0 && refund < ONE_NEAR);
+    }
+}
@@ -1,13 +1,24 @@
+//! Tests for the NEP-171 core methods.
 #![deny(warnings)]
 #![warn(missing_docs)]
 
+/// Shared test helpers.
 pub mod context;
 
-use context::setup_contract;
-use ito_contract::{bridge::SeriesBridge, nft_core::NonFungibleTokenCore, BUY_STORAGE, ONE_NEAR};
-use near_sdk::testing_env;
+use context::{setup_contract, STORAGE_FOR_CREATE_SERIES};
+use ito_contract::{
+    bridge::SeriesBridge,
+    enumeration::NonFungibleTokenEnumeration,
+    nft_core::{NonFungibleTokenCore, NonFungibleTokenResolver},
+    Contract, JsonToken, ONE_NEAR,
+};
+use near_sdk::{
+    test_utils::{get_logs, VMContextBuilder},
+    testing_env, PromiseResult, RuntimeFeesConfig, VMConfig,
+};
+use std::collections::HashMap;
 
-use context::{alice, bob, carol, create_series};
+use context::{alice, bob, carol, create_series, owner};
 
 #[test]
 fn contract_should_return_nft_token_info() {
@@ -69,8 +80,410 @@ fn contract_should_transfer_nft_to_receiver() {
 
     testing_env!(context
         .predecessor_account_id(bob())
-        .attached_deposit(ONE_NEAR + BUY_STORAGE)
+        .attached_deposit(1)
         .build());
     contract.nft_transfer(carol(), token_id, None);
     assert!(contract.is_owner(&trail.token_id, &carol()));
+    assert!(!contract.is_owner(&trail.token_id, &bob()));
+    assert_eq!(
+        contract.get_owned_copies_count(&trail.token_id, &carol()),
+        1
+    );
+    assert_eq!(contract.get_owned_copies_count(&trail.token_id, &bob()), 0);
+}
+
+/// Lists the holders of `series_id` along with their copy counts.
+fn holders_of(contract: &Contract, series_id: &str) -> Vec<(String, u64)> {
+    contract
+        .nft_holders_for_series(series_id.to_string(), None, None)
+        .into_iter()
+        .map(|holder| (holder.account_id.to_string(), holder.copies))
+        .collect()
+}
+
+#[test]
+fn series_holders_should_follow_transfers() {
+    let (mut context, mut contract) = setup_contract();
+    let token_id = start_transfer_call(&mut context, &mut contract);
+    assert_eq!(holders_of(&contract, "1"), [("carol".to_string(), 1)]);
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    contract.nft_buy_series("1".to_string(), carol());
+    assert_eq!(holders_of(&contract, "1"), [("carol".to_string(), 2)]);
+
+    // The receiver refuses the first copy, which goes back to `bob`
+    resolve_transfer(
+        &mut context,
+        &mut contract,
+        &token_id,
+        PromiseResult::Successful(b"true".to_vec()),
+    );
+    assert_eq!(
+        holders_of(&contract, "1"),
+        [("carol".to_string(), 1), ("bob".to_string(), 1)]
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.nft_transfer(carol(), token_id, None);
+    assert_eq!(holders_of(&contract, "1"), [("carol".to_string(), 2)]);
+    assert_eq!(contract.nft_supply_for_series("1".to_string()), 2.into());
+    assert!(!contract.is_owner(&"1".to_string(), &bob()));
+}
+
+/// Buys a copy of a new trail for `bob` and starts transferring it to `carol` with `nft_transfer_call`.
+fn start_transfer_call(context: &mut VMContextBuilder, contract: &mut Contract) -> String {
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(6920000000000000000000)
+        .build());
+    let trail = create_series(
+        contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    let token_id = contract.nft_buy_series(trail.token_id, bob());
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    let _ = contract.nft_transfer_call(carol(), token_id.clone(), None, String::new());
+    assert_eq!(
+        get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"carol","token_ids":["1:1"]}]}"#
+        ]
+    );
+
+    token_id
+}
+
+/// Calls `nft_resolve_transfer` as the contract itself, with `result` as the outcome of `nft_on_transfer`.
+fn resolve_transfer(
+    context: &mut VMContextBuilder,
+    contract: &mut Contract,
+    token_id: &str,
+    result: PromiseResult,
+) -> bool {
+    testing_env!(
+        context
+            .current_account_id(owner())
+            .predecessor_account_id(owner())
+            .build(),
+        VMConfig::default(),
+        RuntimeFeesConfig::default(),
+        HashMap::default(),
+        vec![result],
+    );
+    contract.nft_resolve_transfer(bob(), carol(), token_id.to_string(), HashMap::new(), None)
+}
+
+#[test]
+fn contract_should_not_log_again_when_receiver_keeps_the_token() {
+    let (mut context, mut contract) = setup_contract();
+    let token_id = start_transfer_call(&mut context, &mut contract);
+
+    assert!(resolve_transfer(
+        &mut context,
+        &mut contract,
+        &token_id,
+        PromiseResult::Successful(b"false".to_vec()),
+    ));
+    assert_eq!(
+        contract.tokens_by_id.get(&token_id).unwrap().owner_id,
+        carol()
+    );
+    assert!(get_logs().is_empty());
+}
+
+#[test]
+fn contract_should_log_return_when_receiver_refuses_the_token() {
+    let (mut context, mut contract) = setup_contract();
+    let token_id = start_transfer_call(&mut context, &mut contract);
+
+    assert!(!resolve_transfer(
+        &mut context,
+        &mut contract,
+        &token_id,
+        PromiseResult::Successful(b"true".to_vec()),
+    ));
+    assert_eq!(
+        contract.tokens_by_id.get(&token_id).unwrap().owner_id,
+        bob()
+    );
+    assert!(contract.tokens_per_owner.get(&carol()).is_none());
+    assert_eq!(
+        get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"carol","new_owner_id":"bob","token_ids":["1:1"]}]}"#
+        ]
+    );
+}
+
+#[test]
+fn contract_should_log_memo_of_callbacks_scheduled_before_v4_on_return() {
+    let (mut context, mut contract) = setup_contract();
+    let token_id = start_transfer_call(&mut context, &mut contract);
+
+    testing_env!(
+        context
+            .current_account_id(owner())
+            .predecessor_account_id(owner())
+            .build(),
+        VMConfig::default(),
+        RuntimeFeesConfig::default(),
+        HashMap::default(),
+        vec![PromiseResult::Successful(b"true".to_vec())],
+    );
+    assert!(!contract.nft_resolve_transfer(
+        bob(),
+        carol(),
+        token_id,
+        HashMap::new(),
+        Some("gift".to_string()),
+    ));
+    assert_eq!(
+        get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"carol","new_owner_id":"bob","token_ids":["1:1"],"memo":"gift"}]}"#
+        ]
+    );
+}
+
+#[test]
+fn contract_should_log_return_when_receiver_panics() {
+    let (mut context, mut contract) = setup_contract();
+    let token_id = start_transfer_call(&mut context, &mut contract);
+
+    assert!(!resolve_transfer(
+        &mut context,
+        &mut contract,
+        &token_id,
+        PromiseResult::Failed,
+    ));
+    assert_eq!(
+        contract.tokens_by_id.get(&token_id).unwrap().owner_id,
+        bob()
+    );
+    assert_eq!(get_logs().len(), 1);
+    assert!(get_logs()[0].contains(r#""old_owner_id":"carol","new_owner_id":"bob""#));
+}
+
+#[test]
+fn contract_should_not_log_when_token_is_burned_during_the_call() {
+    let (mut context, mut contract) = setup_contract();
+    let token_id = start_transfer_call(&mut context, &mut contract);
+    contract.tokens_by_id.remove(&token_id);
+
+    assert!(resolve_transfer(
+        &mut context,
+        &mut contract,
+        &token_id,
+        PromiseResult::Successful(b"true".to_vec()),
+    ));
+    assert!(get_logs().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_transfer_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(6920000000000000000000)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    let token_id = contract.nft_buy_series(trail.token_id, bob());
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(0)
+        .build());
+    contract.nft_transfer(carol(), token_id, None);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn contract_should_reject_transfer_call_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(6920000000000000000000)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    let token_id = contract.nft_buy_series(trail.token_id, bob());
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(0)
+        .build());
+    let _ = contract.nft_transfer_call(carol(), token_id, None, String::new());
+}
+
+/// Lists the IDs of `tokens`.
+fn token_ids<T: Into<JsonToken>>(tokens: Vec<T>) -> Vec<String> {
+    tokens
+        .into_iter()
+        .map(|token| token.into().token_id)
+        .collect()
+}
+
+#[test]
+fn owner_tokens_should_start_at_owner_position() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        None,
+        None,
+        None,
+    );
+    for receiver in [bob(), carol(), bob(), carol(), bob()] {
+        contract.nft_mint(trail.token_id.clone(), receiver);
+    }
+
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_owner(bob(), Some(1.into()), None)),
+        ["1:3", "1:5"]
+    );
+    assert_eq!(
+        token_ids(contract.nft_tokens_for_owner(carol(), Some(1.into()), Some(1))),
+        ["1:4"]
+    );
+    // Paging by count goes through every token exactly once
+    let pages: Vec<String> = (0..3)
+        .flat_map(|page| {
+            token_ids(contract.trail_tickets_for_owner(bob(), Some(page.into()), Some(1)))
+        })
+        .collect();
+    assert_eq!(pages, ["1:1", "1:3", "1:5"]);
+    assert!(contract
+        .trail_tickets_for_owner(bob(), Some(3.into()), None)
+        .is_empty());
+    assert!(contract
+        .trail_tickets_for_owner(bob(), Some(u128::MAX.into()), None)
+        .is_empty());
+}
+
+#[test]
+fn owner_tokens_should_stay_in_mint_order_across_transfers() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(0.into()),
+        None,
+        None,
+        None,
+    );
+    for _ in 0..4 {
+        contract.nft_mint(trail.token_id.clone(), bob());
+    }
+
+    // `1:1` leaves and comes back last
+    for (sender, receiver) in [(bob(), carol()), (carol(), bob())] {
+        testing_env!(context
+            .predecessor_account_id(sender)
+            .attached_deposit(1)
+            .build());
+        contract.nft_transfer(receiver, "1:1".to_string(), None);
+    }
+
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_owner(bob(), None, None)),
+        ["1:1", "1:2", "1:3", "1:4"]
+    );
+    assert_eq!(
+        token_ids(contract.nft_tokens_for_owner(bob(), Some(1.into()), Some(2))),
+        ["1:2", "1:3"]
+    );
+    assert_eq!(
+        token_ids(contract.nft_tokens(Some(3.into()), None)),
+        ["1:4"]
+    );
+
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_owner_after(bob(), None, Some(3))),
+        ["1:1", "1:2", "1:3"]
+    );
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_owner_after(bob(), Some("1:3".to_string()), Some(3))),
+        ["1:4"]
+    );
+    assert!(contract
+        .trail_tickets_for_owner_after(bob(), Some("1:4".to_string()), None)
+        .is_empty());
+    assert!(contract
+        .trail_tickets_for_owner_after(carol(), None, None)
+        .is_empty());
+
+    // A cursor no longer owned by the account still marks the position
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.nft_transfer(carol(), "1:2".to_string(), None);
+    assert_eq!(
+        token_ids(contract.trail_tickets_for_owner_after(bob(), Some("1:2".to_string()), None)),
+        ["1:3", "1:4"]
+    );
 }
@@ -0,0 +1,253 @@
+pub mod context;
+
+use ito_contract::{
+    bridge::SeriesBridge, create_serie::CreateTrailSeries, nft_core::NonFungibleTokenCore,
+    pause::PausableOperation, pause::PauseBridge, roles::Role, roles::RolesBridge, ONE_NEAR,
+};
+use near_sdk::testing_env;
+
+use context::{alice, bob, carol, create_series, owner, setup_contract, STORAGE_FOR_CREATE_SERIES};
+
+#[test]
+fn guardian_should_pause_and_unpause_operations() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Guardian, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    assert!(contract.pause(PausableOperation::Buy));
+    assert!(!contract.pause(PausableOperation::Buy));
+    assert!(contract.pause(PausableOperation::Transfer));
+    assert!(contract.is_paused(PausableOperation::Buy));
+    assert!(!contract.is_paused(PausableOperation::Mint));
+    assert_eq!(
+        contract.get_paused_operations(),
+        vec![PausableOperation::Buy, PausableOperation::Transfer]
+    );
+
+    assert!(contract.unpause(PausableOperation::Buy));
+    assert!(!contract.unpause(PausableOperation::Buy));
+    assert!(!contract.is_paused(PausableOperation::Buy));
+    assert_eq!(
+        contract.get_paused_operations(),
+        vec![PausableOperation::Transfer]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner, Admin or Guardian can execute")]
+fn regular_account_should_not_pause() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+
+    contract.pause(PausableOperation::Buy);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Operation `Buy` is paused (E508)")]
+fn contract_should_reject_buying_when_paused() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.pause(PausableOperation::Buy);
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    contract.nft_buy_series(trail.token_id, bob());
+}
+
+#[test]
+fn contract_should_allow_minting_when_only_buying_is_paused() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.pause(PausableOperation::Buy);
+
+    testing_env!(context
+        .predecessor_account_id(carol())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    contract.nft_mint(trail.token_id.clone(), bob());
+    assert!(contract.is_owner(&trail.token_id, &bob()));
+}
+
+#[test]
+#[should_panic(expected = "Campground: Operation `Mint` is paused")]
+fn contract_should_reject_minting_when_paused() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.pause(PausableOperation::Mint);
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    contract.nft_mint(trail.token_id, bob());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Operation `Create` is paused")]
+fn contract_should_reject_creating_trails_when_paused() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.pause(PausableOperation::Create);
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: Operation `UpdatePrice` is paused")]
+fn contract_should_reject_price_changes_when_paused() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        None,
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.pause(PausableOperation::UpdatePrice);
+
+    testing_env!(context.predecessor_account_id(alice()).build());
+    contract.update_trail_series_price(trail.token_id, 0.into());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Operation `Transfer` is paused")]
+fn contract_should_reject_transfers_when_paused() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        Some(ONE_NEAR.into()),
+        Some(10),
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    let token_id = contract.nft_buy_series(trail.token_id, bob());
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.pause(PausableOperation::Transfer);
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(1)
+        .build());
+    contract.nft_transfer(carol(), token_id, None);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn guardian_should_not_pause_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(0)
+        .build());
+    contract.pause(PausableOperation::Buy);
+}
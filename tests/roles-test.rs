@@ -0,0 +1,316 @@
+pub mod context;
+
+use ito_contract::{
+    admin::AdminBridge, bridge::SeriesBridge, config::ConfigBridge, config::ConfigEntry,
+    create_serie::CreateTrailSeries, roles::Role, roles::RolesBridge, ONE_NEAR,
+};
+use near_sdk::testing_env;
+
+use context::{
+    alice, bob, carol, create_series, execute_pending_admin_changes, owner, setup_contract,
+    STORAGE_FOR_CREATE_SERIES,
+};
+
+#[test]
+fn owner_should_grant_and_revoke_roles() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+
+    assert!(!contract.has_role(Role::Moderator, alice()));
+    assert!(contract.grant_role(Role::Moderator, alice()));
+    assert!(!contract.grant_role(Role::Moderator, alice()));
+    assert!(contract.grant_role(Role::Moderator, bob()));
+    assert!(contract.has_role(Role::Moderator, alice()));
+    assert!(contract.has_role(Role::Moderator, bob()));
+    assert!(!contract.has_role(Role::SubAdmin, alice()));
+    assert_eq!(
+        contract.get_role_members(Role::Moderator),
+        vec![alice(), bob()]
+    );
+
+    assert!(contract.revoke_role(Role::Moderator, alice()));
+    assert!(!contract.revoke_role(Role::Moderator, alice()));
+    assert!(!contract.has_role(Role::Moderator, alice()));
+    assert_eq!(contract.get_role_members(Role::Moderator), vec![bob()]);
+    assert!(contract.get_role_members(Role::FinanceManager).is_empty());
+}
+
+#[test]
+fn admin_should_grant_non_admin_roles() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Admin, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FinanceManager, bob());
+    assert!(contract.has_role(Role::FinanceManager, bob()));
+
+    contract.revoke_role(Role::FiatMinter, carol());
+    assert!(!contract.has_role(Role::FiatMinter, carol()));
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner can execute")]
+fn admin_should_not_grant_admin_role() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Admin, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Admin, bob());
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner or Admin can execute")]
+fn regular_account_should_not_grant_roles() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FiatMinter, alice());
+}
+
+#[test]
+fn finance_manager_should_change_campground_fee() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FinanceManager, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.change_campground_fee(10);
+    execute_pending_admin_changes(&mut context, &mut contract);
+    assert_eq!(contract.campground_fee, 10);
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only contract owner or Admin can execute")]
+fn finance_manager_should_not_set_config() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FinanceManager, alice());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(1)
+        .build());
+    contract.set_config(ConfigEntry::MaxPrice(ONE_NEAR.into()));
+}
+
+#[test]
+fn moderator_should_archive_any_trail() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Moderator, bob());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    contract.archive_trail_series(trail.token_id.clone());
+    assert!(contract.is_trail_archived(&trail.token_id));
+
+    contract.unarchive_trail_series(trail.token_id.clone());
+    assert!(!contract.is_trail_archived(&trail.token_id));
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only Moderator can execute")]
+fn regular_account_should_not_archive_others_trail() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(bob())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    contract.archive_trail_series(trail.token_id);
+}
+
+#[test]
+fn any_fiat_minter_should_mint() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::FiatMinter, bob());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        Some(2),
+        None,
+        None,
+    );
+
+    for minter in [carol(), bob()] {
+        testing_env!(context
+            .predecessor_account_id(minter)
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.nft_mint(trail.token_id.clone(), alice());
+    }
+    assert_eq!(
+        contract.get_trail_by_id(&trail.token_id).supply.circulating,
+        2
+    );
+}
+
+#[test]
+#[should_panic(expected = "Campground: Only FiatMinter can execute")]
+fn revoked_fiat_minter_should_not_mint() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.revoke_role(Role::FiatMinter, carol());
+
+    testing_env!(context
+        .predecessor_account_id(carol())
+        .attached_deposit(ONE_NEAR)
+        .build());
+    contract.nft_mint(trail.token_id, alice());
+}
+
+#[test]
+fn owner_and_admin_should_mint_and_moderate_without_roles() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(1)
+        .build());
+    contract.grant_role(Role::Admin, bob());
+
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let trail = create_series(
+        &mut contract,
+        "CampgroundTest",
+        None,
+        None,
+        None,
+        Some(2),
+        None,
+        None,
+    );
+
+    for account in [owner(), bob()] {
+        testing_env!(context
+            .predecessor_account_id(account)
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.nft_mint(trail.token_id.clone(), alice());
+
+        contract.archive_trail_series(trail.token_id.clone());
+        assert!(contract.is_trail_archived(&trail.token_id));
+        contract.unarchive_trail_series(trail.token_id.clone());
+        assert!(!contract.is_trail_archived(&trail.token_id));
+    }
+    assert_eq!(
+        contract.get_trail_by_id(&trail.token_id).supply.circulating,
+        2
+    );
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn owner_should_not_grant_roles_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(0)
+        .build());
+    contract.grant_role(Role::Moderator, alice());
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn owner_should_not_revoke_roles_without_one_yocto() {
+    let (mut context, mut contract) = setup_contract();
+    testing_env!(context
+        .predecessor_account_id(owner())
+        .attached_deposit(0)
+        .build());
+    contract.revoke_role(Role::FiatMinter, carol());
+}
@@ -0,0 +1,220 @@
+pub mod context;
+
+use ito_contract::{
+    create_serie::CreateTrailSeries,
+    nft_core::NonFungibleTokenCore,
+    storage::{StorageEstimate, StorageEstimateBridge},
+    Contract, TrailResource, TrailSeriesMetadata, ONE_NEAR,
+};
+use near_sdk::{env, json_types::U128, testing_env, AccountId, Balance};
+
+use context::{alice, bob, carol, owner, setup_contract, STORAGE_FOR_CREATE_SERIES};
+
+fn metadata(title: &str) -> TrailSeriesMetadata {
+    TrailSeriesMetadata {
+        title: title.to_owned(),
+        description: "Some description".to_owned(),
+        tickets_amount: 10,
+        media: Some("https://arweave.net/media.png".to_owned()),
+        data: None,
+        resources: vec![TrailResource {
+            title: None,
+            description: None,
+            media: "https://arweave.net/image.png".to_owned(),
+            extra: None,
+            reference: None,
+        }],
+        starts_at: None,
+        expires_at: None,
+        reference: None,
+        campground_id: "CMPGR123".to_owned(),
+        media_hash: None,
+        fallback_media: None,
+    }
+}
+
+/// Bytes added to the contract storage by `call`, `0` when it frees storage.
+fn storage_added(call: impl FnOnce()) -> u64 {
+    let initial_storage_usage = env::storage_usage();
+    call();
+    env::storage_usage().saturating_sub(initial_storage_usage)
+}
+
+fn storage_cost(bytes: u64) -> U128 {
+    (Balance::from(bytes) * env::storage_byte_cost()).into()
+}
+
+/// Creates a trail as `creator`, checking it against its estimate.
+fn create_checked(
+    context: &mut near_sdk::test_utils::VMContextBuilder,
+    contract: &mut Contract,
+    creator: AccountId,
+    title: &str,
+    allow_user_minting: Option<bool>,
+    slug: Option<String>,
+) {
+    testing_env!(context
+        .predecessor_account_id(creator.clone())
+        .attached_deposit(STORAGE_FOR_CREATE_SERIES)
+        .build());
+    let estimate = contract.create_trail_series_estimated(
+        metadata(title),
+        Some(ONE_NEAR.into()),
+        Some(creator.clone()),
+        None,
+        allow_user_minting,
+        slug.clone(),
+    );
+    let upper_bound = contract.create_trail_series_estimated(
+        metadata(title),
+        Some(ONE_NEAR.into()),
+        None,
+        None,
+        allow_user_minting,
+        slug.clone(),
+    );
+
+    let bytes = storage_added(|| {
+        contract.create_trail_series(
+            metadata(title),
+            Some(ONE_NEAR.into()),
+            None,
+            None,
+            allow_user_minting,
+            slug,
+        );
+    });
+    assert_eq!(estimate, Some(storage_cost(bytes)));
+    assert!(upper_bound.unwrap().0 >= estimate.unwrap().0);
+}
+
+#[test]
+fn create_estimate_should_match_storage_used() {
+    let (mut context, mut contract) = setup_contract();
+
+    create_checked(&mut context, &mut contract, alice(), "First", None, None);
+    create_checked(
+        &mut context,
+        &mut contract,
+        alice(),
+        "Second",
+        Some(false),
+        None,
+    );
+    create_checked(
+        &mut context,
+        &mut contract,
+        bob(),
+        "Slugged",
+        None,
+        Some("slugged-trail".to_owned()),
+    );
+}
+
+/// Mints the next copy of `series_id` for `receiver` as its creator, checking it against its estimate.
+fn mint_checked(
+    context: &mut near_sdk::test_utils::VMContextBuilder,
+    contract: &mut Contract,
+    series_id: &str,
+    receiver: AccountId,
+) -> String {
+    let estimate = contract.nft_mint_estimated(series_id.to_owned(), receiver.clone());
+    testing_env!(context
+        .predecessor_account_id(alice())
+        .attached_deposit(estimate.deposit.0)
+        .build());
+
+    let mut token_id = String::new();
+    let bytes = storage_added(|| token_id = contract.nft_mint(series_id.to_owned(), receiver));
+    assert_eq!(
+        estimate,
+        StorageEstimate {
+            bytes: bytes.into(),
+            storage_cost: storage_cost(bytes),
+            deposit: storage_cost(bytes),
+        }
+    );
+    token_id
+}
+
+#[test]
+fn mint_estimate_should_match_storage_used() {
+    let (mut context, mut contract) = setup_contract();
+    create_checked(&mut context, &mut contract, alice(), "First", None, None);
+    create_checked(&mut context, &mut contract, alice(), "Second", None, None);
+
+    // First copy of the series and of the owner
+    mint_checked(&mut context, &mut contract, "1", bob());
+    // Another copy for the same holder
+    mint_checked(&mut context, &mut contract, "1", bob());
+    // A new holder of the series
+    mint_checked(&mut context, &mut contract, "1", carol());
+    // First copy of a series for an existing owner
+    mint_checked(&mut context, &mut contract, "2", bob());
+}
+
+#[test]
+fn buy_estimate_should_match_storage_used() {
+    let (mut context, mut contract) = setup_contract();
+    create_checked(&mut context, &mut contract, alice(), "First", None, None);
+
+    for receiver in [bob(), bob(), carol()] {
+        let estimate = contract.nft_buy_series_estimated("1".to_owned(), receiver.clone());
+        assert_eq!(
+            estimate.deposit,
+            contract.nft_get_series_price("1".to_owned())
+        );
+
+        testing_env!(context
+            .predecessor_account_id(bob())
+            .attached_deposit(estimate.deposit.0)
+            .build());
+        let bytes = storage_added(|| {
+            contract.nft_buy_series("1".to_owned(), receiver);
+        });
+        assert_eq!(estimate.bytes, bytes.into());
+        assert_eq!(estimate.storage_cost, storage_cost(bytes));
+    }
+}
+
+/// Transfers `token_id` from `sender` to `receiver`, checking it against its estimate.
+fn transfer_checked(
+    context: &mut near_sdk::test_utils::VMContextBuilder,
+    contract: &mut Contract,
+    token_id: &str,
+    sender: AccountId,
+    receiver: AccountId,
+) {
+    let estimate = contract.nft_transfer_estimated(receiver.clone(), token_id.to_owned());
+    testing_env!(context
+        .predecessor_account_id(sender)
+        .attached_deposit(estimate.deposit.0)
+        .build());
+
+    let bytes = storage_added(|| contract.nft_transfer(receiver, token_id.to_owned(), None));
+    assert_eq!(
+        estimate,
+        StorageEstimate {
+            bytes: bytes.into(),
+            storage_cost: storage_cost(bytes),
+            deposit: 1.into(),
+        }
+    );
+}
+
+#[test]
+fn transfer_estimate_should_match_storage_used() {
+    let (mut context, mut contract) = setup_contract();
+    create_checked(&mut context, &mut contract, alice(), "First", None, None);
+    let first = mint_checked(&mut context, &mut contract, "1", bob());
+    let second = mint_checked(&mut context, &mut contract, "1", bob());
+
+    // To a new owner, while the sender keeps another copy
+    transfer_checked(&mut context, &mut contract, &first, bob(), carol());
+    // To an existing holder, leaving the sender without copies
+    transfer_checked(&mut context, &mut contract, &second, bob(), carol());
+    // To a new owner with a longer account id, from the only holder
+    transfer_checked(&mut context, &mut contract, &first, carol(), owner());
+    // Back to an account that held copies before
+    transfer_checked(&mut context, &mut contract, &first, owner(), bob());
+}